@@ -0,0 +1,290 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdResult,
+    SubMsg, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw721_base::InstantiateMsg as Cw721InstantiateMsg;
+use cw_storage_plus::Bound;
+use cw_utils::{maybe_addr, parse_reply_instantiate_data, Duration, Threshold};
+use sg_daos::{Admin, ContractInstantiateMsg};
+use sg_gov::msg::{
+    Group, GroupResponse, InstantiateMsg as GovInstantiateMsg, QueryMsg as GovQueryMsg,
+};
+use sg_nft_group::msg::InstantiateMsg as GroupInstantiateMsg;
+
+use crate::error::ContractError;
+use crate::msg::{DaoListResponse, DaoResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{daos, Config, Dao, CONFIG, PENDING_DAO};
+
+// version info for migration info
+pub const CONTRACT_NAME: &str = "crates.io:sg-dao-factory";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const INIT_DAO_REPLY_ID: u64 = 1;
+
+/// Symbol of the internal collections holding membership NFTs
+const MEMBER_COLLECTION_SYMBOL: &str = "SGMC";
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        gov_code_id: msg.gov_code_id,
+        group_code_id: msg.group_code_id,
+        cw721_code_id: msg.cw721_code_id,
+        registry: maybe_addr(deps.api, msg.registry)?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateDao {
+            name,
+            description,
+            image,
+            collection,
+            threshold,
+            voting_period,
+        } => execute_create_dao(
+            deps,
+            env,
+            info,
+            name,
+            description,
+            image,
+            collection,
+            threshold,
+            voting_period,
+        ),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_create_dao(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    description: String,
+    image: String,
+    collection: String,
+    threshold: Threshold,
+    voting_period: Duration,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let collection = deps.api.addr_validate(&collection)?;
+
+    // the group replaces the minter with itself, and administers its member collection
+    let cw721_init_msg = ContractInstantiateMsg {
+        code_id: config.cw721_code_id,
+        msg: to_binary(&Cw721InstantiateMsg {
+            name: format!("{} Members", name),
+            symbol: MEMBER_COLLECTION_SYMBOL.to_string(),
+            minter: env.contract.address.to_string(),
+        })?,
+        admin: Some(Admin::Creator {}),
+        label: format!("{} Members", name),
+    };
+    // the DAO instantiates the group, and administers it
+    let group_init_msg = ContractInstantiateMsg {
+        code_id: config.group_code_id,
+        msg: to_binary(&GroupInstantiateMsg {
+            collection: collection.to_string(),
+            cw721_init_msg,
+        })?,
+        admin: Some(Admin::Creator {}),
+        label: format!("{} Group", name),
+    };
+    let gov_init_msg = GovInstantiateMsg {
+        name: name.clone(),
+        description,
+        image,
+        group: Group::Cw4Instantiate(group_init_msg),
+        threshold,
+        max_voting_period: voting_period,
+        min_voting_period: None,
+        grace_period: None,
+        executor: None,
+        parent: None,
+        registry: config.registry.map(String::from),
+    };
+
+    // the DAO address is not known yet, the factory hands over the admin on reply
+    let msg = WasmMsg::Instantiate {
+        admin: Some(env.contract.address.to_string()),
+        code_id: config.gov_code_id,
+        msg: to_binary(&gov_init_msg)?,
+        funds: vec![],
+        label: name.clone(),
+    };
+    PENDING_DAO.save(
+        deps.storage,
+        &(name, collection.clone(), info.sender.clone()),
+    )?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(msg, INIT_DAO_REPLY_ID))
+        .add_attribute("action", "create_dao")
+        .add_attribute("collection", collection)
+        .add_attribute("creator", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != INIT_DAO_REPLY_ID {
+        return Err(ContractError::InvalidReplyID {});
+    }
+
+    let res = parse_reply_instantiate_data(msg).map_err(|_| ContractError::ReplyOnSuccess {})?;
+    let dao_addr = deps.api.addr_validate(&res.contract_address)?;
+
+    // the group was instantiated by the DAO before it replied
+    let GroupResponse { group } = deps
+        .querier
+        .query_wasm_smart(&dao_addr, &GovQueryMsg::Group {})?;
+
+    let (name, collection, creator) = PENDING_DAO.load(deps.storage)?;
+    PENDING_DAO.remove(deps.storage);
+    let dao = Dao {
+        name,
+        collection,
+        group: group.addr(),
+        creator,
+    };
+    daos().save(deps.storage, &dao_addr, &dao)?;
+
+    let msg = WasmMsg::UpdateAdmin {
+        contract_addr: dao_addr.to_string(),
+        admin: dao_addr.to_string(),
+    };
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "dao_created")
+        .add_attribute("dao", dao_addr)
+        .add_attribute("group", dao.group))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Dao { address } => to_binary(&query_dao(deps, address)?),
+        QueryMsg::ListDaos { start_after, limit } => {
+            to_binary(&list_daos(deps, start_after, limit)?)
+        }
+        QueryMsg::ListDaosByCollection {
+            collection,
+            start_after,
+            limit,
+        } => to_binary(&list_daos_by_collection(
+            deps,
+            collection,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ListDaosByCreator {
+            creator,
+            start_after,
+            limit,
+        } => to_binary(&list_daos_by_creator(deps, creator, start_after, limit)?),
+    }
+}
+
+fn dao_response(address: Addr, dao: Dao) -> DaoResponse {
+    DaoResponse {
+        address,
+        name: dao.name,
+        collection: dao.collection,
+        group: dao.group,
+        creator: dao.creator,
+    }
+}
+
+fn query_dao(deps: Deps, address: String) -> StdResult<DaoResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let dao = daos().load(deps.storage, &address)?;
+    Ok(dao_response(address, dao))
+}
+
+fn list_daos(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DaoListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let daos = daos()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(address, dao)| dao_response(address, dao)))
+        .collect::<StdResult<_>>()?;
+    Ok(DaoListResponse { daos })
+}
+
+fn list_daos_by_collection(
+    deps: Deps,
+    collection: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DaoListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let collection = deps.api.addr_validate(&collection)?;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let daos = daos()
+        .idx
+        .collection
+        .prefix(collection)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(address, dao)| dao_response(address, dao)))
+        .collect::<StdResult<_>>()?;
+    Ok(DaoListResponse { daos })
+}
+
+fn list_daos_by_creator(
+    deps: Deps,
+    creator: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DaoListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let creator = deps.api.addr_validate(&creator)?;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let daos = daos()
+        .idx
+        .creator
+        .prefix(creator)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(address, dao)| dao_response(address, dao)))
+        .collect::<StdResult<_>>()?;
+    Ok(DaoListResponse { daos })
+}
@@ -0,0 +1,305 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{to_binary, Addr, Empty, WasmMsg};
+    use cw3::Vote;
+    use cw721_base::{
+        msg::{ExecuteMsg as Cw721ExecuteMsg, InstantiateMsg as Cw721InstantiateMsg},
+        Extension, MintMsg,
+    };
+    use cw_multi_test::{Contract, ContractWrapper, Executor};
+    use cw_utils::{Duration, Threshold};
+    use sg_gov::msg::{
+        ExecuteMsg as GovExecuteMsg, MetadataResponse, ProposerVote, QueryMsg as GovQueryMsg,
+    };
+    use sg_multi_test::StargazeApp;
+    use sg_std::StargazeMsgWrapper;
+
+    use crate::msg::{DaoListResponse, DaoResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+
+    const CREATOR1: &str = "creator0001";
+    const CREATOR2: &str = "creator0002";
+    const MINTER: &str = "minter0001";
+    const MEMBER: &str = "member0001";
+
+    const COLLECTION_CONTRACT: &str = "contract0";
+    const OTHER_COLLECTION_CONTRACT: &str = "contract1";
+    const FACTORY_CONTRACT: &str = "contract2";
+    const DAO_CONTRACT: &str = "contract3";
+    const GROUP_CONTRACT: &str = "contract4";
+    const MEMBER_COLLECTION_CONTRACT: &str = "contract5";
+
+    fn contract_factory() -> Box<dyn Contract<StargazeMsgWrapper>> {
+        let contract = ContractWrapper::new_with_empty(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        )
+        .with_reply_empty(crate::contract::reply);
+        Box::new(contract)
+    }
+
+    fn contract_gov() -> Box<dyn Contract<StargazeMsgWrapper>> {
+        let contract = ContractWrapper::new(
+            sg_gov::contract::execute,
+            sg_gov::contract::instantiate,
+            sg_gov::contract::query,
+        )
+        .with_reply(sg_gov::contract::reply);
+        Box::new(contract)
+    }
+
+    fn contract_nft_group() -> Box<dyn Contract<StargazeMsgWrapper>> {
+        let contract = ContractWrapper::new_with_empty(
+            sg_nft_group::contract::execute,
+            sg_nft_group::contract::instantiate,
+            sg_nft_group::contract::query,
+        )
+        .with_reply_empty(sg_nft_group::contract::reply);
+        Box::new(contract)
+    }
+
+    fn contract_cw721() -> Box<dyn Contract<StargazeMsgWrapper>> {
+        let contract = ContractWrapper::new_with_empty(
+            cw721_base::entry::execute,
+            cw721_base::entry::instantiate,
+            cw721_base::entry::query,
+        );
+        Box::new(contract)
+    }
+
+    fn instantiate_collection(app: &mut StargazeApp, cw721_id: u64) -> Addr {
+        let msg = Cw721InstantiateMsg {
+            name: "My NFTs".to_string(),
+            symbol: "NFT".to_string(),
+            minter: MINTER.to_string(),
+        };
+        app.instantiate_contract(
+            cw721_id,
+            Addr::unchecked(MINTER),
+            &msg,
+            &[],
+            "collection",
+            None,
+        )
+        .unwrap()
+    }
+
+    /// Instantiates two collections and the factory
+    fn setup_factory(app: &mut StargazeApp) -> Addr {
+        let cw721_id = app.store_code(contract_cw721());
+        instantiate_collection(app, cw721_id);
+        instantiate_collection(app, cw721_id);
+
+        let msg = InstantiateMsg {
+            gov_code_id: app.store_code(contract_gov()),
+            group_code_id: app.store_code(contract_nft_group()),
+            cw721_code_id: cw721_id,
+            registry: None,
+        };
+        let factory_id = app.store_code(contract_factory());
+        app.instantiate_contract(
+            factory_id,
+            Addr::unchecked(CREATOR1),
+            &msg,
+            &[],
+            "factory",
+            None,
+        )
+        .unwrap()
+    }
+
+    fn create_dao(app: &mut StargazeApp, creator: &str, name: &str, collection: &str) {
+        let msg = ExecuteMsg::CreateDao {
+            name: name.to_string(),
+            description: "description".to_string(),
+            image: "image".to_string(),
+            collection: collection.to_string(),
+            threshold: Threshold::AbsoluteCount { weight: 1 },
+            voting_period: Duration::Time(3600),
+        };
+        app.execute_contract(
+            Addr::unchecked(creator),
+            Addr::unchecked(FACTORY_CONTRACT),
+            &msg,
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn list(app: &StargazeApp, msg: QueryMsg) -> Vec<Addr> {
+        let res: DaoListResponse = app.wrap().query_wasm_smart(FACTORY_CONTRACT, &msg).unwrap();
+        res.daos.into_iter().map(|dao| dao.address).collect()
+    }
+
+    #[test]
+    fn factory_creates_working_dao() {
+        let mut app = StargazeApp::new();
+        setup_factory(&mut app);
+        create_dao(&mut app, CREATOR1, "dao", COLLECTION_CONTRACT);
+
+        let dao: DaoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                FACTORY_CONTRACT,
+                &QueryMsg::Dao {
+                    address: DAO_CONTRACT.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            dao,
+            DaoResponse {
+                address: Addr::unchecked(DAO_CONTRACT),
+                name: "dao".to_string(),
+                collection: Addr::unchecked(COLLECTION_CONTRACT),
+                group: Addr::unchecked(GROUP_CONTRACT),
+                creator: Addr::unchecked(CREATOR1),
+            }
+        );
+
+        // the DAO administers itself and its group, the group its member collection
+        let admin = |app: &StargazeApp, addr: &str| {
+            app.wrap()
+                .query_wasm_contract_info(addr)
+                .unwrap()
+                .admin
+                .unwrap()
+        };
+        assert_eq!(admin(&app, DAO_CONTRACT), DAO_CONTRACT);
+        assert_eq!(admin(&app, GROUP_CONTRACT), DAO_CONTRACT);
+        assert_eq!(admin(&app, MEMBER_COLLECTION_CONTRACT), GROUP_CONTRACT);
+
+        let collection: String = app
+            .wrap()
+            .query_wasm_smart(GROUP_CONTRACT, &sg_nft_group::msg::QueryMsg::Collection {})
+            .unwrap();
+        assert_eq!(collection, COLLECTION_CONTRACT);
+
+        // holders join the group and govern the DAO
+        app.execute_contract(
+            Addr::unchecked(MINTER),
+            Addr::unchecked(COLLECTION_CONTRACT),
+            &Cw721ExecuteMsg::Mint::<Extension, Empty>(MintMsg {
+                token_id: "1".to_string(),
+                owner: MEMBER.to_string(),
+                token_uri: None,
+                extension: None,
+            }),
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(MEMBER),
+            Addr::unchecked(COLLECTION_CONTRACT),
+            &Cw721ExecuteMsg::<Extension, Empty>::SendNft {
+                contract: GROUP_CONTRACT.to_string(),
+                token_id: "1".to_string(),
+                msg: Default::default(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let update_metadata = GovExecuteMsg::UpdateMetadata {
+            name: "renamed".to_string(),
+            description: "description".to_string(),
+            image: "image".to_string(),
+        };
+        app.update_block(|block| block.height += 1);
+        app.execute_contract(
+            Addr::unchecked(MEMBER),
+            Addr::unchecked(DAO_CONTRACT),
+            &GovExecuteMsg::Propose {
+                title: "rename".to_string(),
+                description: "rename".to_string(),
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: DAO_CONTRACT.to_string(),
+                    msg: to_binary(&update_metadata).unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+                actions: vec![],
+                metadata: None,
+                latest: None,
+                execution_mode: None,
+                proposer_vote: Some(ProposerVote::Cast(Vote::Yes)),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(MEMBER),
+            Addr::unchecked(DAO_CONTRACT),
+            &GovExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap();
+
+        let metadata: MetadataResponse = app
+            .wrap()
+            .query_wasm_smart(DAO_CONTRACT, &GovQueryMsg::Metadata {})
+            .unwrap();
+        assert_eq!(metadata.name, "renamed");
+    }
+
+    #[test]
+    fn registry_lists_daos_by_collection_and_creator() {
+        let mut app = StargazeApp::new();
+        setup_factory(&mut app);
+        // every DAO instantiates 3 contracts
+        create_dao(&mut app, CREATOR1, "first", COLLECTION_CONTRACT);
+        create_dao(&mut app, CREATOR2, "second", COLLECTION_CONTRACT);
+        create_dao(&mut app, CREATOR1, "third", OTHER_COLLECTION_CONTRACT);
+        let first = Addr::unchecked("contract3");
+        let second = Addr::unchecked("contract6");
+        let third = Addr::unchecked("contract9");
+
+        let all = list(
+            &app,
+            QueryMsg::ListDaos {
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(all, vec![first.clone(), second.clone(), third.clone()]);
+
+        let page = list(
+            &app,
+            QueryMsg::ListDaos {
+                start_after: Some(first.to_string()),
+                limit: Some(1),
+            },
+        );
+        assert_eq!(page, vec![second.clone()]);
+
+        let by_collection = list(
+            &app,
+            QueryMsg::ListDaosByCollection {
+                collection: COLLECTION_CONTRACT.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(by_collection, vec![first.clone(), second.clone()]);
+
+        let by_collection = list(
+            &app,
+            QueryMsg::ListDaosByCollection {
+                collection: COLLECTION_CONTRACT.to_string(),
+                start_after: Some(first.to_string()),
+                limit: None,
+            },
+        );
+        assert_eq!(by_collection, vec![second]);
+
+        let by_creator = list(
+            &app,
+            QueryMsg::ListDaosByCreator {
+                creator: CREATOR1.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(by_creator, vec![first, third]);
+    }
+}
@@ -0,0 +1,71 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Addr;
+use cw_utils::{Duration, Threshold};
+
+use crate::state::Config;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub gov_code_id: u64,
+    pub group_code_id: u64,
+    /// code of the internal collection holding the membership NFTs of a group
+    pub cw721_code_id: u64,
+    /// registry the created DAOs register themselves with
+    pub registry: Option<String>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Instantiate an sg-gov governed by an sg-nft-group of `collection`.
+    /// The DAO is its own admin and the admin of its group.
+    CreateDao {
+        name: String,
+        description: String,
+        image: String,
+        /// the collection whose NFTs give membership
+        collection: String,
+        threshold: Threshold,
+        voting_period: Duration,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+    #[returns(DaoResponse)]
+    Dao { address: String },
+    #[returns(DaoListResponse)]
+    ListDaos {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// DAOs governed by holders of `collection`
+    #[returns(DaoListResponse)]
+    ListDaosByCollection {
+        collection: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(DaoListResponse)]
+    ListDaosByCreator {
+        creator: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct DaoResponse {
+    pub address: Addr,
+    pub name: String,
+    pub collection: Addr,
+    pub group: Addr,
+    pub creator: Addr,
+}
+
+#[cw_serde]
+pub struct DaoListResponse {
+    pub daos: Vec<DaoResponse>,
+}
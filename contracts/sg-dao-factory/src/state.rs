@@ -0,0 +1,49 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+
+#[cw_serde]
+pub struct Config {
+    pub gov_code_id: u64,
+    pub group_code_id: u64,
+    /// code of the internal collection holding the membership NFTs of a group
+    pub cw721_code_id: u64,
+    /// registry the created DAOs register themselves with
+    pub registry: Option<Addr>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// A DAO created by the factory
+#[cw_serde]
+pub struct Dao {
+    pub name: String,
+    /// the collection whose NFTs give membership
+    pub collection: Addr,
+    pub group: Addr,
+    pub creator: Addr,
+}
+
+/// (name, collection, creator) of the DAO being instantiated, registered on reply
+pub const PENDING_DAO: Item<(String, Addr, Addr)> = Item::new("pending_dao");
+
+pub struct DaoIndexes<'a> {
+    pub collection: MultiIndex<'a, Addr, Dao, &'a Addr>,
+    pub creator: MultiIndex<'a, Addr, Dao, &'a Addr>,
+}
+
+impl<'a> IndexList<Dao> for DaoIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Dao>> + '_> {
+        let v: Vec<&dyn Index<Dao>> = vec![&self.collection, &self.creator];
+        Box::new(v.into_iter())
+    }
+}
+
+/// sg-gov address -> DAO
+pub fn daos<'a>() -> IndexedMap<'a, &'a Addr, Dao, DaoIndexes<'a>> {
+    let indexes = DaoIndexes {
+        collection: MultiIndex::new(|_, dao| dao.collection.clone(), "daos", "daos__collection"),
+        creator: MultiIndex::new(|_, dao| dao.creator.clone(), "daos", "daos__creator"),
+    };
+    IndexedMap::new("daos", indexes)
+}
@@ -0,0 +1,289 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use cw_utils::maybe_addr;
+use sg_gov::msg::{GroupResponse, MetadataResponse, QueryMsg as GovQueryMsg};
+use sg_nft_group::msg::QueryMsg as NftGroupQueryMsg;
+
+use crate::error::ContractError;
+use crate::msg::{DaoListResponse, DaoResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{daos, name_key, Config, Dao, GroupType, CONFIG, DAO_NAMES};
+
+// version info for migration info
+pub const CONTRACT_NAME: &str = "crates.io:sg-dao-registry";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            admin: maybe_addr(deps.api, msg.admin)?,
+            gov_code_ids: msg.gov_code_ids,
+        },
+    )?;
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Register {} => execute_register(deps, info),
+        ExecuteMsg::Deregister {} => execute_deregister(deps, info),
+        ExecuteMsg::UpdateCodeIds { gov_code_ids } => {
+            execute_update_code_ids(deps, info, gov_code_ids)
+        }
+    }
+}
+
+pub fn execute_register(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    // any contract can answer the sg-gov queries, so the code of the sender is checked
+    let code_id = deps
+        .querier
+        .query_wasm_contract_info(&info.sender)
+        .map_err(|_| ContractError::NotDao {})?
+        .code_id;
+    if !CONFIG.load(deps.storage)?.gov_code_ids.contains(&code_id) {
+        return Err(ContractError::NotDao {});
+    }
+
+    // the entry is built from the DAO itself
+    let MetadataResponse { name, .. } = deps
+        .querier
+        .query_wasm_smart(&info.sender, &GovQueryMsg::Metadata {})
+        .map_err(|_| ContractError::NotDao {})?;
+    let GroupResponse { group } = deps
+        .querier
+        .query_wasm_smart(&info.sender, &GovQueryMsg::Group {})
+        .map_err(|_| ContractError::NotDao {})?;
+
+    // only sg-nft-group answers the collection query
+    let collection: Option<String> = deps
+        .querier
+        .query_wasm_smart(group.addr(), &NftGroupQueryMsg::Collection {})
+        .ok();
+    let (group_type, collection) = match collection {
+        Some(collection) => (
+            GroupType::NftGroup,
+            Some(deps.api.addr_validate(&collection)?),
+        ),
+        None => (GroupType::Cw4, None),
+    };
+
+    let dao = Dao {
+        name,
+        group: group.addr(),
+        group_type,
+        collection,
+    };
+    if let Some(old) = daos().may_load(deps.storage, &info.sender)? {
+        DAO_NAMES.remove(deps.storage, &name_key(&old.name, &info.sender));
+    }
+    DAO_NAMES.save(
+        deps.storage,
+        &name_key(&dao.name, &info.sender),
+        &info.sender,
+    )?;
+    daos().save(deps.storage, &info.sender, &dao)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register")
+        .add_attribute("dao", info.sender)
+        .add_attribute("name", dao.name))
+}
+
+pub fn execute_deregister(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    // a DAO that was never listed has nothing to remove
+    if let Some(dao) = daos().may_load(deps.storage, &info.sender)? {
+        DAO_NAMES.remove(deps.storage, &name_key(&dao.name, &info.sender));
+        daos().remove(deps.storage, &info.sender)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "deregister")
+        .add_attribute("dao", info.sender))
+}
+
+pub fn execute_update_code_ids(
+    deps: DepsMut,
+    info: MessageInfo,
+    gov_code_ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.admin.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    config.gov_code_ids = gov_code_ids;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_code_ids"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Dao { address } => to_binary(&query_dao(deps, address)?),
+        QueryMsg::ListDaos { start_after, limit } => {
+            to_binary(&list_daos(deps, start_after, limit)?)
+        }
+        QueryMsg::ListDaosByGroupType {
+            group_type,
+            start_after,
+            limit,
+        } => to_binary(&list_daos_by_group_type(
+            deps,
+            group_type,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ListDaosByCollection {
+            collection,
+            start_after,
+            limit,
+        } => to_binary(&list_daos_by_collection(
+            deps,
+            collection,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ListDaosByName {
+            name,
+            start_after,
+            limit,
+        } => to_binary(&list_daos_by_name(deps, name, start_after, limit)?),
+    }
+}
+
+fn dao_response(address: Addr, dao: Dao) -> DaoResponse {
+    DaoResponse {
+        address,
+        name: dao.name,
+        group: dao.group,
+        group_type: dao.group_type,
+        collection: dao.collection,
+    }
+}
+
+fn query_dao(deps: Deps, address: String) -> StdResult<DaoResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let dao = daos().load(deps.storage, &address)?;
+    Ok(dao_response(address, dao))
+}
+
+fn dao_list(
+    daos: impl Iterator<Item = StdResult<(Addr, Dao)>>,
+    limit: Option<u32>,
+) -> StdResult<DaoListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let daos = daos
+        .take(limit)
+        .map(|item| item.map(|(address, dao)| dao_response(address, dao)))
+        .collect::<StdResult<_>>()?;
+    Ok(DaoListResponse { daos })
+}
+
+fn list_daos(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DaoListResponse> {
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+    dao_list(
+        daos().range(deps.storage, start, None, Order::Ascending),
+        limit,
+    )
+}
+
+fn list_daos_by_group_type(
+    deps: Deps,
+    group_type: GroupType,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DaoListResponse> {
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+    dao_list(
+        daos().idx.group_type.prefix(group_type as u8).range(
+            deps.storage,
+            start,
+            None,
+            Order::Ascending,
+        ),
+        limit,
+    )
+}
+
+fn list_daos_by_collection(
+    deps: Deps,
+    collection: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DaoListResponse> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+    dao_list(
+        daos().idx.collection.prefix(collection.to_string()).range(
+            deps.storage,
+            start,
+            None,
+            Order::Ascending,
+        ),
+        limit,
+    )
+}
+
+fn list_daos_by_name(
+    deps: Deps,
+    name: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DaoListResponse> {
+    let prefix = name.to_lowercase();
+    let start_after = match maybe_addr(deps.api, start_after)? {
+        Some(addr) => Some(name_key(&daos().load(deps.storage, &addr)?.name, &addr)),
+        None => None,
+    };
+    let start = match &start_after {
+        Some(key) => Bound::exclusive(key.as_str()),
+        None => Bound::inclusive(prefix.as_str()),
+    };
+    let daos = DAO_NAMES
+        .range(deps.storage, Some(start), None, Order::Ascending)
+        .take_while(|item| match item {
+            Ok((key, _)) => key.starts_with(&prefix),
+            Err(_) => true,
+        })
+        .map(|item| {
+            let (_, addr) = item?;
+            let dao = daos().load(deps.storage, &addr)?;
+            Ok((addr, dao))
+        })
+        // a shorter name can match when the prefix contains the separator
+        .filter(|item: &StdResult<(Addr, Dao)>| match item {
+            Ok((_, dao)) => dao.name.to_lowercase().starts_with(&prefix),
+            Err(_) => true,
+        });
+    dao_list(daos, limit)
+}
@@ -0,0 +1,14 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Only sg-gov contracts can register")]
+    NotDao {},
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+}
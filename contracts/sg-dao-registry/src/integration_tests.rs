@@ -0,0 +1,599 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{to_binary, Addr, WasmMsg};
+    use cw3::Vote;
+    use cw4::Member;
+    use cw721_base::InstantiateMsg as Cw721InstantiateMsg;
+    use cw_multi_test::{Contract, ContractWrapper, Executor};
+    use cw_utils::{Duration, Threshold};
+    use sg_daos::{Admin, ContractInstantiateMsg};
+    use sg_gov::msg::{
+        ExecuteMsg as GovExecuteMsg, Group, InstantiateMsg as GovInstantiateMsg, ProposerVote,
+    };
+    use sg_multi_test::StargazeApp;
+    use sg_std::StargazeMsgWrapper;
+
+    use crate::msg::{DaoListResponse, DaoResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+    use crate::state::{Config, GroupType};
+    use crate::ContractError;
+
+    const OWNER: &str = "admin0001";
+    const MINTER: &str = "minter0001";
+    const MEMBER: &str = "member0001";
+
+    const COLLECTION_CONTRACT: &str = "contract0";
+    const REGISTRY_CONTRACT: &str = "contract1";
+    const NFT_DAO_CONTRACT: &str = "contract2";
+    const NFT_GROUP_CONTRACT: &str = "contract3";
+    const CW4_GROUP_CONTRACT: &str = "contract5";
+    const CW4_DAO_CONTRACT: &str = "contract6";
+    const OTHER_NFT_DAO_CONTRACT: &str = "contract7";
+
+    fn contract_registry() -> Box<dyn Contract<StargazeMsgWrapper>> {
+        let contract = ContractWrapper::new_with_empty(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    fn contract_gov() -> Box<dyn Contract<StargazeMsgWrapper>> {
+        let contract = ContractWrapper::new(
+            sg_gov::contract::execute,
+            sg_gov::contract::instantiate,
+            sg_gov::contract::query,
+        )
+        .with_reply(sg_gov::contract::reply);
+        Box::new(contract)
+    }
+
+    fn contract_nft_group() -> Box<dyn Contract<StargazeMsgWrapper>> {
+        let contract = ContractWrapper::new_with_empty(
+            sg_nft_group::contract::execute,
+            sg_nft_group::contract::instantiate,
+            sg_nft_group::contract::query,
+        )
+        .with_reply_empty(sg_nft_group::contract::reply);
+        Box::new(contract)
+    }
+
+    fn contract_cw4_group() -> Box<dyn Contract<StargazeMsgWrapper>> {
+        let contract = ContractWrapper::new_with_empty(
+            cw4_group::contract::execute,
+            cw4_group::contract::instantiate,
+            cw4_group::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    fn contract_cw721() -> Box<dyn Contract<StargazeMsgWrapper>> {
+        let contract = ContractWrapper::new_with_empty(
+            cw721_base::entry::execute,
+            cw721_base::entry::instantiate,
+            cw721_base::entry::query,
+        );
+        Box::new(contract)
+    }
+
+    fn gov_init_msg(name: &str, group: Group) -> GovInstantiateMsg {
+        GovInstantiateMsg {
+            name: name.to_string(),
+            description: "description".to_string(),
+            image: "image".to_string(),
+            group,
+            threshold: Threshold::AbsoluteCount { weight: 1 },
+            max_voting_period: Duration::Time(3600),
+            min_voting_period: None,
+            grace_period: None,
+            executor: None,
+            parent: None,
+            registry: Some(REGISTRY_CONTRACT.to_string()),
+        }
+    }
+
+    /// DAO instantiating an sg-nft-group of the collection
+    fn instantiate_nft_dao(app: &mut StargazeApp, gov_id: u64, name: &str) -> Addr {
+        let cw721_id = app.store_code(contract_cw721());
+        let group_id = app.store_code(contract_nft_group());
+
+        let cw721_init_msg = ContractInstantiateMsg {
+            code_id: cw721_id,
+            msg: to_binary(&Cw721InstantiateMsg {
+                name: "MemberCollection".to_string(),
+                symbol: "SGMC".to_string(),
+                minter: MINTER.to_string(),
+            })
+            .unwrap(),
+            admin: Some(Admin::Creator {}),
+            label: "MemberCollection".to_string(),
+        };
+        let group = Group::Cw4Instantiate(ContractInstantiateMsg {
+            code_id: group_id,
+            msg: to_binary(&sg_nft_group::msg::InstantiateMsg {
+                collection: COLLECTION_CONTRACT.to_string(),
+                cw721_init_msg,
+            })
+            .unwrap(),
+            admin: Some(Admin::Creator {}),
+            label: "group".to_string(),
+        });
+        app.instantiate_contract(
+            gov_id,
+            Addr::unchecked(OWNER),
+            &gov_init_msg(name, group),
+            &[],
+            "dao",
+            None,
+        )
+        .unwrap()
+    }
+
+    /// DAO of a cw4-group with MEMBER as its only member
+    fn instantiate_cw4_dao(app: &mut StargazeApp, gov_id: u64, name: &str) -> Addr {
+        let cw4_group_id = app.store_code(contract_cw4_group());
+
+        let group_addr = app
+            .instantiate_contract(
+                cw4_group_id,
+                Addr::unchecked(OWNER),
+                &cw4_group::msg::InstantiateMsg {
+                    admin: None,
+                    members: vec![Member {
+                        addr: MEMBER.to_string(),
+                        weight: 1,
+                    }],
+                },
+                &[],
+                "group",
+                None,
+            )
+            .unwrap();
+        app.instantiate_contract(
+            gov_id,
+            Addr::unchecked(OWNER),
+            &gov_init_msg(name, Group::Cw4Address(group_addr.to_string())),
+            &[],
+            "dao",
+            None,
+        )
+        .unwrap()
+    }
+
+    /// Returns the sg-gov code id allowed to register
+    fn setup_registry(app: &mut StargazeApp) -> u64 {
+        let cw721_id = app.store_code(contract_cw721());
+        app.instantiate_contract(
+            cw721_id,
+            Addr::unchecked(MINTER),
+            &Cw721InstantiateMsg {
+                name: "My NFTs".to_string(),
+                symbol: "NFT".to_string(),
+                minter: MINTER.to_string(),
+            },
+            &[],
+            "collection",
+            None,
+        )
+        .unwrap();
+
+        let gov_id = app.store_code(contract_gov());
+        let registry_id = app.store_code(contract_registry());
+        app.instantiate_contract(
+            registry_id,
+            Addr::unchecked(OWNER),
+            &InstantiateMsg {
+                admin: Some(OWNER.to_string()),
+                gov_code_ids: vec![gov_id],
+            },
+            &[],
+            "registry",
+            None,
+        )
+        .unwrap();
+        gov_id
+    }
+
+    fn list(app: &StargazeApp, msg: QueryMsg) -> Vec<Addr> {
+        let res: DaoListResponse = app
+            .wrap()
+            .query_wasm_smart(REGISTRY_CONTRACT, &msg)
+            .unwrap();
+        res.daos.into_iter().map(|dao| dao.address).collect()
+    }
+
+    #[test]
+    fn daos_register_on_instantiation() {
+        let mut app = StargazeApp::new();
+        let gov_id = setup_registry(&mut app);
+        let nft_dao = instantiate_nft_dao(&mut app, gov_id, "Collectors");
+        let cw4_dao = instantiate_cw4_dao(&mut app, gov_id, "Council");
+        let other_nft_dao = instantiate_nft_dao(&mut app, gov_id, "collectors");
+        assert_eq!(nft_dao, NFT_DAO_CONTRACT);
+        assert_eq!(cw4_dao, CW4_DAO_CONTRACT);
+        assert_eq!(other_nft_dao, OTHER_NFT_DAO_CONTRACT);
+
+        let query_dao = |app: &StargazeApp, address: &Addr| -> DaoResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    REGISTRY_CONTRACT,
+                    &QueryMsg::Dao {
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap()
+        };
+        assert_eq!(
+            query_dao(&app, &nft_dao),
+            DaoResponse {
+                address: nft_dao.clone(),
+                name: "Collectors".to_string(),
+                group: Addr::unchecked(NFT_GROUP_CONTRACT),
+                group_type: GroupType::NftGroup,
+                collection: Some(Addr::unchecked(COLLECTION_CONTRACT)),
+            }
+        );
+        assert_eq!(
+            query_dao(&app, &cw4_dao),
+            DaoResponse {
+                address: cw4_dao.clone(),
+                name: "Council".to_string(),
+                group: Addr::unchecked(CW4_GROUP_CONTRACT),
+                group_type: GroupType::Cw4,
+                collection: None,
+            }
+        );
+
+        let all = list(
+            &app,
+            QueryMsg::ListDaos {
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(
+            all,
+            vec![nft_dao.clone(), cw4_dao.clone(), other_nft_dao.clone()]
+        );
+
+        let nft_daos = list(
+            &app,
+            QueryMsg::ListDaosByGroupType {
+                group_type: GroupType::NftGroup,
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(nft_daos, vec![nft_dao.clone(), other_nft_dao.clone()]);
+        let cw4_daos = list(
+            &app,
+            QueryMsg::ListDaosByGroupType {
+                group_type: GroupType::Cw4,
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(cw4_daos, vec![cw4_dao.clone()]);
+
+        let page = list(
+            &app,
+            QueryMsg::ListDaosByCollection {
+                collection: COLLECTION_CONTRACT.to_string(),
+                start_after: Some(nft_dao.to_string()),
+                limit: Some(1),
+            },
+        );
+        assert_eq!(page, vec![other_nft_dao.clone()]);
+
+        // names match ignoring case
+        let by_name = list(
+            &app,
+            QueryMsg::ListDaosByName {
+                name: "COLLECTORS".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(by_name, vec![nft_dao.clone(), other_nft_dao.clone()]);
+
+        // and by prefix, ordered by name
+        let by_prefix = list(
+            &app,
+            QueryMsg::ListDaosByName {
+                name: "Co".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(
+            by_prefix,
+            vec![nft_dao.clone(), other_nft_dao.clone(), cw4_dao.clone()]
+        );
+        let page = list(
+            &app,
+            QueryMsg::ListDaosByName {
+                name: "co".to_string(),
+                start_after: Some(nft_dao.to_string()),
+                limit: Some(1),
+            },
+        );
+        assert_eq!(page, vec![other_nft_dao.clone()]);
+        let page = list(
+            &app,
+            QueryMsg::ListDaosByName {
+                name: "co".to_string(),
+                start_after: Some(other_nft_dao.to_string()),
+                limit: None,
+            },
+        );
+        assert_eq!(page, vec![cw4_dao]);
+        let by_prefix = list(
+            &app,
+            QueryMsg::ListDaosByName {
+                name: "collectorss".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert!(by_prefix.is_empty());
+    }
+
+    #[test]
+    fn renamed_dao_refreshes_its_entry() {
+        let mut app = StargazeApp::new();
+        let gov_id = setup_registry(&mut app);
+        let dao = instantiate_cw4_dao(&mut app, gov_id, "Council");
+
+        let update_metadata = GovExecuteMsg::UpdateMetadata {
+            name: "Guild".to_string(),
+            description: "description".to_string(),
+            image: "image".to_string(),
+        };
+        app.update_block(|block| block.height += 1);
+        app.execute_contract(
+            Addr::unchecked(MEMBER),
+            dao.clone(),
+            &GovExecuteMsg::Propose {
+                title: "rename".to_string(),
+                description: "rename".to_string(),
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: dao.to_string(),
+                    msg: to_binary(&update_metadata).unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+                actions: vec![],
+                metadata: None,
+                latest: None,
+                execution_mode: None,
+                proposer_vote: Some(ProposerVote::Cast(Vote::Yes)),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(MEMBER),
+            dao.clone(),
+            &GovExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap();
+
+        let by_name = |app: &StargazeApp, name: &str| {
+            list(
+                app,
+                QueryMsg::ListDaosByName {
+                    name: name.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+        };
+        assert!(by_name(&app, "council").is_empty());
+        assert_eq!(by_name(&app, "guild"), vec![dao]);
+    }
+
+    #[test]
+    fn only_daos_register() {
+        let mut app = StargazeApp::new();
+        let gov_id = setup_registry(&mut app);
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked(MEMBER),
+                Addr::unchecked(REGISTRY_CONTRACT),
+                &ExecuteMsg::Register {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::NotDao {}, err.downcast().unwrap());
+
+        // answering the sg-gov queries is not enough without an allowed sg-gov code
+        let dao = instantiate_cw4_dao(&mut app, gov_id, "Council");
+        let entry: DaoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                REGISTRY_CONTRACT,
+                &QueryMsg::Dao {
+                    address: dao.to_string(),
+                },
+            )
+            .unwrap();
+        let other_gov_id = app.store_code(contract_gov());
+        let err = app
+            .instantiate_contract(
+                other_gov_id,
+                Addr::unchecked(OWNER),
+                &gov_init_msg("Council", Group::Cw4Address(entry.group.to_string())),
+                &[],
+                "dao",
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::NotDao {}, err.downcast().unwrap());
+        let all = list(
+            &app,
+            QueryMsg::ListDaos {
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(all, vec![dao]);
+    }
+
+    #[test]
+    fn only_admin_updates_code_ids() {
+        let mut app = StargazeApp::new();
+        let gov_id = setup_registry(&mut app);
+        let other_gov_id = app.store_code(contract_gov());
+
+        let update = ExecuteMsg::UpdateCodeIds {
+            gov_code_ids: vec![other_gov_id],
+        };
+        let err = app
+            .execute_contract(
+                Addr::unchecked(MEMBER),
+                Addr::unchecked(REGISTRY_CONTRACT),
+                &update,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+        let dao = instantiate_cw4_dao(&mut app, gov_id, "Council");
+
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(REGISTRY_CONTRACT),
+            &update,
+            &[],
+        )
+        .unwrap();
+        let config: Config = app
+            .wrap()
+            .query_wasm_smart(REGISTRY_CONTRACT, &QueryMsg::Config {})
+            .unwrap();
+        assert_eq!(config.gov_code_ids, vec![other_gov_id]);
+
+        // DAOs of the old code can no longer register, those already listed stay
+        let entry: DaoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                REGISTRY_CONTRACT,
+                &QueryMsg::Dao {
+                    address: dao.to_string(),
+                },
+            )
+            .unwrap();
+        let group = Group::Cw4Address(entry.group.to_string());
+        let err = app
+            .instantiate_contract(
+                gov_id,
+                Addr::unchecked(OWNER),
+                &gov_init_msg("Guild", group.clone()),
+                &[],
+                "dao",
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::NotDao {}, err.downcast().unwrap());
+        let other_dao = app
+            .instantiate_contract(
+                other_gov_id,
+                Addr::unchecked(OWNER),
+                &gov_init_msg("Guild", group),
+                &[],
+                "dao",
+                None,
+            )
+            .unwrap();
+        let all = list(
+            &app,
+            QueryMsg::ListDaos {
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(all, vec![dao, other_dao]);
+    }
+
+    #[test]
+    fn dissolved_dao_deregisters() {
+        let mut app = StargazeApp::new();
+        let gov_id = setup_registry(&mut app);
+        let dao = instantiate_cw4_dao(&mut app, gov_id, "Council");
+        let entry: DaoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                REGISTRY_CONTRACT,
+                &QueryMsg::Dao {
+                    address: dao.to_string(),
+                },
+            )
+            .unwrap();
+
+        // a sub-DAO of MEMBER
+        let sub_dao = app
+            .instantiate_contract(
+                gov_id,
+                Addr::unchecked(OWNER),
+                &GovInstantiateMsg {
+                    parent: Some(MEMBER.to_string()),
+                    ..gov_init_msg("Committee", Group::Cw4Address(entry.group.to_string()))
+                },
+                &[],
+                "dao",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked(MEMBER),
+            sub_dao.clone(),
+            &GovExecuteMsg::Dissolve {},
+            &[],
+        )
+        .unwrap();
+        app.wrap()
+            .query_wasm_smart::<DaoResponse>(
+                REGISTRY_CONTRACT,
+                &QueryMsg::Dao {
+                    address: sub_dao.to_string(),
+                },
+            )
+            .unwrap_err();
+        let by_name = list(
+            &app,
+            QueryMsg::ListDaosByName {
+                name: "committee".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert!(by_name.is_empty());
+        let all = list(
+            &app,
+            QueryMsg::ListDaos {
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(all, vec![dao.clone()]);
+
+        // only the sender is removed
+        app.execute_contract(
+            Addr::unchecked(MEMBER),
+            Addr::unchecked(REGISTRY_CONTRACT),
+            &ExecuteMsg::Deregister {},
+            &[],
+        )
+        .unwrap();
+        let all = list(
+            &app,
+            QueryMsg::ListDaos {
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(all, vec![dao]);
+    }
+}
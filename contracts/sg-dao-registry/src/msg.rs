@@ -0,0 +1,73 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Addr;
+
+use crate::state::{Config, GroupType};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// can update the code ids, they are fixed without one
+    pub admin: Option<String>,
+    /// only contracts instantiated from these sg-gov codes can register
+    pub gov_code_ids: Vec<u64>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// List the sending sg-gov contract, or refresh its entry.
+    /// Its name and group are queried from the DAO.
+    Register {},
+    /// Remove the sending DAO from the registry, sent by sg-gov when it is dissolved
+    Deregister {},
+    /// Replace the sg-gov codes that can register (admin only).
+    /// DAOs already listed are kept.
+    UpdateCodeIds { gov_code_ids: Vec<u64> },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+    #[returns(DaoResponse)]
+    Dao { address: String },
+    #[returns(DaoListResponse)]
+    ListDaos {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(DaoListResponse)]
+    ListDaosByGroupType {
+        group_type: GroupType,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// DAOs governed by holders of `collection`
+    #[returns(DaoListResponse)]
+    ListDaosByCollection {
+        collection: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// DAOs whose name starts with `name`, ignoring case, ordered by name then address.
+    /// `start_after` is the address of the last DAO of the previous page.
+    #[returns(DaoListResponse)]
+    ListDaosByName {
+        name: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct DaoResponse {
+    pub address: Addr,
+    pub name: String,
+    pub group: Addr,
+    pub group_type: GroupType,
+    pub collection: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct DaoListResponse {
+    pub daos: Vec<DaoResponse>,
+}
@@ -0,0 +1,73 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+
+#[cw_serde]
+pub struct Config {
+    /// can update the code ids, they are fixed without one
+    pub admin: Option<Addr>,
+    /// only contracts instantiated from these sg-gov codes can register
+    pub gov_code_ids: Vec<u64>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum GroupType {
+    /// sg-nft-group, membership given by the NFTs of a collection
+    NftGroup,
+    /// any other cw4 group
+    Cw4,
+}
+
+/// A registered DAO
+#[cw_serde]
+pub struct Dao {
+    pub name: String,
+    pub group: Addr,
+    pub group_type: GroupType,
+    /// the collection whose NFTs give membership, for NFT groups
+    pub collection: Option<Addr>,
+}
+
+pub struct DaoIndexes<'a> {
+    pub group_type: MultiIndex<'a, u8, Dao, &'a Addr>,
+    /// empty for DAOs without a collection
+    pub collection: MultiIndex<'a, String, Dao, &'a Addr>,
+}
+
+impl<'a> IndexList<Dao> for DaoIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Dao>> + '_> {
+        let v: Vec<&dyn Index<Dao>> = vec![&self.group_type, &self.collection];
+        Box::new(v.into_iter())
+    }
+}
+
+/// sg-gov address -> DAO
+pub fn daos<'a>() -> IndexedMap<'a, &'a Addr, Dao, DaoIndexes<'a>> {
+    let indexes = DaoIndexes {
+        group_type: MultiIndex::new(|_, dao| dao.group_type as u8, "daos", "daos__group_type"),
+        collection: MultiIndex::new(
+            |_, dao| {
+                dao.collection
+                    .as_ref()
+                    .map(Addr::to_string)
+                    .unwrap_or_default()
+            },
+            "daos",
+            "daos__collection",
+        ),
+    };
+    IndexedMap::new("daos", indexes)
+}
+
+/// `name_key` -> sg-gov address.
+/// Index keys of an `IndexedMap` are length-prefixed, so names are kept here to be
+/// searched by prefix.
+pub const DAO_NAMES: Map<&str, Addr> = Map::new("dao_names");
+
+/// Lowercase name of the DAO followed by its address, ordered by name then address
+pub fn name_key(name: &str, dao: &Addr) -> String {
+    format!("{}\0{}", name.to_lowercase(), dao)
+}
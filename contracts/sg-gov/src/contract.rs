@@ -1,30 +1,68 @@
 use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 
+use cosmwasm_schema::cw_serde;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, BlockInfo, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
-    Response, StdResult, SubMsg,
+    coin, coins, from_binary, to_binary, to_vec, Addr, Api, BankMsg, Binary, BlockInfo,
+    CanonicalAddr, Coin, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Order, QuerierWrapper,
+    Reply, StdError, StdResult, Storage, SubMsgResult, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
-use cw3::{
-    ProposalListResponse, ProposalResponse, Status, Vote, VoteInfo, VoteListResponse, VoteResponse,
-    VoterDetail, VoterListResponse, VoterResponse,
+use cw20::{
+    BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, Denom, TokenInfoResponse,
 };
-use cw3_fixed_multisig::state::{next_id, Ballot, Proposal, Votes, BALLOTS, PROPOSALS};
+use cw3::{Status, Vote, VoterDetail, VoterListResponse, VoterResponse};
+use cw3_fixed_multisig::state::{Ballot, Votes, BALLOTS};
 use cw4::Cw4Contract;
+use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, Cw721ReceiveMsg, OwnerOfResponse};
 use cw_storage_plus::Bound;
-use cw_utils::{maybe_addr, parse_reply_instantiate_data, Expiration, ThresholdResponse};
+use cw_utils::{
+    maybe_addr, must_pay, parse_reply_instantiate_data, Duration, Expiration, Threshold,
+    ThresholdResponse,
+};
+use ripemd::Ripemd160;
+use sg_daos::{
+    swap::{Asset, AssetInfo, PairContract, SwapAdapter},
+    Admin, ContractInstantiateMsg, RagequitMsg,
+};
+use sg_std::{CosmosMsg, Response, StargazeMsg, StargazeMsgWrapper, SubMsg};
+use sha2::{Digest, Sha256};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, Group, GroupResponse, InstantiateMsg, MetadataResponse, QueryMsg};
-use crate::state::{Config, CONFIG, GROUP};
+use crate::msg::{
+    ActionResponse, AllowanceListResponse, AllowanceResponse, AuctionListResponse, AuctionResponse,
+    CollectionListResponse, Cw20Balance, Delegation, DelegationListResponse, DelegationResponse,
+    ExecuteMsg, ExecutionResultsResponse, ExecutionStatus, Group, GroupResponse, InstantiateMsg,
+    MemberParticipationResponse, MessageSimulation, MetadataResponse, NftListResponse,
+    NonceResponse, ParentResponse, ParticipationStatsResponse, ProposalListResponse,
+    ProposalResponse, ProposalTurnout, ProposerVote, QueryMsg, RevenueSplitResponse, SignedVote,
+    SignedVotePayload, SimulationResponse, StreamListResponse, StreamResponse, SubDaoListResponse,
+    SubDaoResponse, TreasuryBalancesResponse, VoteInfo, VoteListResponse, VoteResponse,
+    VotingPowerResponse,
+};
+use crate::state::{
+    denom_key, execution_reply_id, next_auction_id, next_proposal_id, next_stream_id,
+    parse_execution_reply_id, proposal_status, proposals, record_turnout, Allowance, Auction, Bid,
+    Config, DelegatedVote, ExecutionMode, Executor, MessageResult, Proposal, ProposalAction,
+    ProposalCategory, ProposalMetadata, RevenueSplit, Stream, ALLOWANCES, AUCTIONED_NFTS, AUCTIONS,
+    COLLECTIONS, CONFIG, DELEGATED_VOTES, DELEGATIONS, DELEGATORS, DISSOLVED, ESCROWED_BIDS,
+    EXECUTION_MODES, EXECUTION_RESULTS, GRACE_ENDS, GROUP, MAX_RATIONALE_LENGTH, MIN_VOTING_ENDS,
+    NFTS, NONCES, PARENT, PARTICIPATION_WINDOW, PROPOSALS_VOTED, RATIONALES, REGISTRY,
+    REVENUE_BASELINES, REVENUE_SPLIT, SETTLING_AUCTION, STREAMS, SUB_DAOS, SWAP_BALANCES, TOKENS,
+    TURNOUTS, VOTER_BALLOTS,
+};
 
 // version info for migration info
 pub const CONTRACT_NAME: &str = "crates.io:sg-gov";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const INIT_GROUP_REPLY_ID: u64 = 1;
+const INIT_SUB_DAO_REPLY_ID: u64 = 2;
+const SWEEP_CW20_REPLY_ID: u64 = 3;
+const SETTLE_AUCTION_REPLY_ID: u64 = 4;
+const SWAP_REPLY_ID: u64 = 5;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -39,6 +77,11 @@ pub fn instantiate(
     // we can consider it invalid. Really though we're just checking the
     // threshold is not 0
     msg.threshold.validate(u64::MAX)?;
+    validate_periods(
+        msg.max_voting_period,
+        msg.min_voting_period,
+        msg.grace_period,
+    )?;
 
     let self_addr = env.contract.address;
 
@@ -48,10 +91,19 @@ pub fn instantiate(
         image: msg.image,
         threshold: msg.threshold.clone(),
         max_voting_period: msg.max_voting_period,
+        min_voting_period: msg.min_voting_period,
+        grace_period: msg.grace_period,
         executor: msg.executor,
     };
     CONFIG.save(deps.storage, &cfg)?;
 
+    if let Some(parent) = msg.parent {
+        PARENT.save(deps.storage, &deps.api.addr_validate(&parent)?)?;
+    }
+    if let Some(registry) = msg.registry {
+        REGISTRY.save(deps.storage, &deps.api.addr_validate(&registry)?)?;
+    }
+
     match msg.group {
         Group::Cw4Instantiate(init) => Ok(Response::default().add_submessage(
             SubMsg::reply_on_success(init.into_wasm_msg(self_addr), INIT_GROUP_REPLY_ID),
@@ -63,9 +115,48 @@ pub fn instantiate(
             msg.threshold.validate(total_group_weight)?;
 
             GROUP.save(deps.storage, &Cw4Contract(group_addr))?;
-            Ok(Response::default())
+            Ok(Response::default().add_messages(register_msg(deps.storage)?))
+        }
+    }
+}
+
+/// Message refreshing the entry of the DAO in its registry, if any
+fn register_msg(storage: &dyn Storage) -> StdResult<Option<WasmMsg>> {
+    REGISTRY
+        .may_load(storage)?
+        .map(sg_daos::register_msg)
+        .transpose()
+}
+
+/// Message removing the DAO from its registry, if any
+fn deregister_msg(storage: &dyn Storage) -> StdResult<Option<WasmMsg>> {
+    REGISTRY
+        .may_load(storage)?
+        .map(sg_daos::deregister_msg)
+        .transpose()
+}
+
+fn validate_periods(
+    max_voting_period: Duration,
+    min_voting_period: Option<Duration>,
+    grace_period: Option<Duration>,
+) -> Result<(), ContractError> {
+    if let Some(min_voting_period) = min_voting_period {
+        match (min_voting_period, max_voting_period) {
+            (Duration::Height(min), Duration::Height(max))
+            | (Duration::Time(min), Duration::Time(max))
+                if min <= max => {}
+            _ => return Err(ContractError::InvalidMinVotingPeriod {}),
         }
     }
+    if let Some(grace_period) = grace_period {
+        match (grace_period, max_voting_period) {
+            (Duration::Height(_), Duration::Height(_)) | (Duration::Time(_), Duration::Time(_)) => {
+            }
+            _ => return Err(ContractError::InvalidGracePeriod {}),
+        }
+    }
+    Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -80,7 +171,11 @@ pub fn execute(
             title,
             description,
             msgs,
+            actions,
+            metadata,
             latest,
+            execution_mode,
+            proposer_vote,
         } => Ok(execute_propose(
             deps,
             env,
@@ -88,10 +183,19 @@ pub fn execute(
             title,
             description,
             msgs,
+            actions,
+            metadata,
             latest,
+            execution_mode,
+            proposer_vote,
         )?),
-        ExecuteMsg::Vote { proposal_id, vote } => {
-            Ok(execute_vote(deps, env, info, proposal_id, vote)?)
+        ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale,
+        } => Ok(execute_vote(deps, env, info, proposal_id, vote, rationale)?),
+        ExecuteMsg::SubmitSignedVotes { votes } => {
+            execute_submit_signed_votes(deps, env, info, votes)
         }
         ExecuteMsg::Execute { proposal_id } => Ok(execute_execute(deps, env, info, proposal_id)?),
         ExecuteMsg::Close { proposal_id } => Ok(execute_close(deps, env, info, proposal_id)?),
@@ -107,9 +211,110 @@ pub fn execute(
             description,
             image,
         )?),
+        ExecuteMsg::UpdateConfig {
+            threshold,
+            max_voting_period,
+            min_voting_period,
+            grace_period,
+            executor,
+        } => execute_update_config(
+            deps,
+            env,
+            info,
+            threshold,
+            max_voting_period,
+            min_voting_period,
+            grace_period,
+            executor,
+        ),
+        ExecuteMsg::Delegate { delegate } => execute_delegate(deps, env, info, delegate),
+        ExecuteMsg::Undelegate {} => execute_undelegate(deps, env, info),
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, info, msg),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, info, msg),
+        ExecuteMsg::UpdateTokenList { to_add, to_remove } => {
+            execute_update_token_list(deps, env, info, to_add, to_remove)
+        }
+        ExecuteMsg::CreateStream {
+            recipient,
+            denom,
+            amount_per_interval,
+            interval,
+            start,
+            end,
+            cliff,
+        } => execute_create_stream(
+            deps,
+            env,
+            info,
+            Stream {
+                recipient: Addr::unchecked(recipient),
+                denom,
+                amount_per_interval,
+                interval,
+                start,
+                end,
+                cliff,
+                claimed: Uint128::zero(),
+            },
+        ),
+        ExecuteMsg::ClaimStream { id } => execute_claim_stream(deps, env, info, id),
+        ExecuteMsg::CancelStream { id } => execute_cancel_stream(deps, env, info, id),
+        ExecuteMsg::SetAllowance {
+            grantee,
+            denom,
+            cap,
+            period,
+        } => execute_set_allowance(deps, env, info, grantee, denom, cap, period),
+        ExecuteMsg::RevokeAllowance { grantee, denom } => {
+            execute_revoke_allowance(deps, env, info, grantee, denom)
+        }
+        ExecuteMsg::Swap {
+            pair,
+            offer,
+            min_receive,
+        } => execute_swap(deps, env, info, pair, offer, min_receive),
+        ExecuteMsg::Ragequit(msg) => execute_ragequit(deps, env, info, msg),
+        ExecuteMsg::StartAuction {
+            collection,
+            token_id,
+            denom,
+            reserve_price,
+            duration,
+        } => execute_start_auction(
+            deps,
+            env,
+            info,
+            collection,
+            token_id,
+            denom,
+            reserve_price,
+            duration,
+        ),
+        ExecuteMsg::Bid { auction_id } => execute_bid(deps, env, info, auction_id),
+        ExecuteMsg::SettleAuction { auction_id } => {
+            execute_settle_auction(deps, env, info, auction_id)
+        }
+        ExecuteMsg::CancelAuction { auction_id } => {
+            execute_cancel_auction(deps, env, info, auction_id)
+        }
+        ExecuteMsg::UpdateRevenueSplit { split } => {
+            execute_update_revenue_split(deps, env, info, split)
+        }
+        ExecuteMsg::Distribute {} => execute_distribute(deps, env, info),
+        ExecuteMsg::Spend { to, denom, amount } => {
+            execute_spend(deps, env, info, to, denom, amount)
+        }
+        ExecuteMsg::CreateSubDao(init) => execute_create_sub_dao(env, info, init),
+        ExecuteMsg::OverrideSubDao { addr, msgs } => {
+            execute_override_sub_dao(deps, env, info, addr, msgs)
+        }
+        ExecuteMsg::DissolveSubDao { addr } => execute_dissolve_sub_dao(deps, env, info, addr),
+        ExecuteMsg::ParentExecute { msgs } => execute_parent_execute(deps, env, info, msgs),
+        ExecuteMsg::Dissolve {} => execute_dissolve(deps, env, info),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_propose(
     deps: DepsMut,
     env: Env,
@@ -117,9 +322,13 @@ pub fn execute_propose(
     title: String,
     description: String,
     msgs: Vec<CosmosMsg>,
+    actions: Vec<ProposalAction>,
+    metadata: Option<ProposalMetadata>,
     // we ignore earliest
     latest: Option<Expiration>,
-) -> Result<Response<Empty>, ContractError> {
+    execution_mode: Option<ExecutionMode>,
+    proposer_vote: Option<ProposerVote>,
+) -> Result<Response, ContractError> {
     // only members of the multisig can create a proposal
     let cfg = CONFIG.load(deps.storage)?;
     let group = GROUP.load(deps.storage)?;
@@ -132,6 +341,16 @@ pub fn execute_propose(
     let vote_power = group
         .is_member(&deps.querier, &info.sender, None)?
         .ok_or(ContractError::Unauthorized {})?;
+
+    ensure_not_dissolved(deps.storage)?;
+
+    for action in &actions {
+        validate_action(deps.api, action)?;
+    }
+    if let Some(metadata) = &metadata {
+        metadata.validate()?;
+    }
+
     // max expires also used as default
     let max_expires = cfg.max_voting_period.after(&env.block);
     let mut expires = latest.unwrap_or(max_expires);
@@ -142,34 +361,75 @@ pub fn execute_propose(
         return Err(ContractError::WrongExpiration {});
     }
 
+    let id = next_proposal_id(deps.storage)?;
+
+    let execution_mode = execution_mode.unwrap_or_default();
+    if execution_mode != ExecutionMode::Atomic {
+        EXECUTION_MODES.save(deps.storage, id, &execution_mode)?;
+    }
+
+    if let Some(min_voting_period) = cfg.min_voting_period {
+        let min_voting_end = min_voting_period.after(&env.block);
+        // the proposal has to stay open for at least the minimum voting period
+        if expires < min_voting_end {
+            return Err(ContractError::WrongExpiration {});
+        }
+        MIN_VOTING_ENDS.save(deps.storage, id, &min_voting_end)?;
+    }
+
+    let ballot = match proposer_vote.unwrap_or(ProposerVote::Cast(Vote::Yes)) {
+        ProposerVote::Cast(vote) => {
+            // the proposer also votes with the weight delegated to them
+            let weight = vote_power
+                + cast_delegated_power(
+                    deps.storage,
+                    &deps.querier,
+                    &group,
+                    id,
+                    &info.sender,
+                    env.block.height,
+                )?;
+            Some(Ballot { weight, vote })
+        }
+        // the proposer and their delegators are free to vote later
+        ProposerVote::Skip {} => None,
+    };
+    let mut votes = Votes::yes(0);
+    if let Some(ballot) = &ballot {
+        votes.add_vote(ballot.vote, ballot.weight);
+    }
+
     // create a proposal
     let mut prop = Proposal {
         title,
         description,
+        proposer: info.sender.clone(),
         start_height: env.block.height,
         expires,
         msgs,
+        actions,
+        metadata,
         status: Status::Open,
-        votes: Votes::yes(vote_power),
+        votes,
         threshold: cfg.threshold,
         total_weight: group.total_weight(&deps.querier)?,
     };
-    prop.update_status(&env.block);
-    let id = next_id(deps.storage)?;
-    PROPOSALS.save(deps.storage, id, &prop)?;
-
-    // add the first yes vote from voter
-    let ballot = Ballot {
-        weight: vote_power,
-        vote: Vote::Yes,
-    };
-    BALLOTS.save(deps.storage, (id, &info.sender), &ballot)?;
+    let status = update_status(deps.storage, id, &mut prop, &env.block)?;
+    proposals().save(deps.storage, id, &prop)?;
+    start_grace_period(deps.storage, id, &prop, &env.block)?;
+
+    // add the first vote from the proposer
+    if let Some(ballot) = ballot {
+        BALLOTS.save(deps.storage, (id, &info.sender), &ballot)?;
+        record_ballot(deps.storage, &info.sender, id)?;
+    }
+    record_turnout(deps.storage, id, &prop)?;
 
     Ok(Response::new()
         .add_attribute("action", "propose")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", id.to_string())
-        .add_attribute("status", format!("{:?}", prop.status)))
+        .add_attribute("status", format!("{:?}", status)))
 }
 
 pub fn execute_vote(
@@ -178,12 +438,51 @@ pub fn execute_vote(
     info: MessageInfo,
     proposal_id: u64,
     vote: Vote,
-) -> Result<Response<Empty>, ContractError> {
+    rationale: Option<String>,
+) -> Result<Response, ContractError> {
+    let status = cast_vote(
+        deps,
+        &env,
+        &info.sender,
+        proposal_id,
+        vote,
+        rationale.clone(),
+    )?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "vote")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("status", format!("{:?}", status));
+    if let Some(rationale) = rationale {
+        res = res.add_attribute("rationale", rationale);
+    }
+
+    Ok(res)
+}
+
+/// Records a ballot for `voter` and returns the updated proposal status
+fn cast_vote(
+    deps: DepsMut,
+    env: &Env,
+    voter: &Addr,
+    proposal_id: u64,
+    vote: Vote,
+    rationale: Option<String>,
+) -> Result<Status, ContractError> {
+    if let Some(rationale) = &rationale {
+        if rationale.chars().count() > MAX_RATIONALE_LENGTH {
+            return Err(ContractError::RationaleTooLong {
+                max: MAX_RATIONALE_LENGTH,
+            });
+        }
+    }
+
     // only members of the multisig can vote
     let group = GROUP.load(deps.storage)?;
 
     // ensure proposal exists and can be voted on
-    let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    let mut prop = proposals().load(deps.storage, proposal_id)?;
     // Allow voting on Passed and Rejected proposals too,
     if ![Status::Open, Status::Passed, Status::Rejected].contains(&prop.status) {
         return Err(ContractError::NotOpen {});
@@ -193,32 +492,235 @@ pub fn execute_vote(
         return Err(ContractError::Expired {});
     }
 
-    // Only voting members of the multisig can vote
-    // Additional check if weight >= 1
+    // Only voting members of the multisig, or addresses that members delegated to, can vote
     // use a snapshot of "start of proposal"
     let vote_power = group
-        .is_voting_member(&deps.querier, &info.sender, prop.start_height)?
-        .ok_or(ContractError::Unauthorized {})?;
+        .is_voting_member(&deps.querier, voter, prop.start_height)?
+        .unwrap_or_default()
+        + cast_delegated_power(
+            deps.storage,
+            &deps.querier,
+            &group,
+            proposal_id,
+            voter,
+            prop.start_height,
+        )?;
+    if vote_power == 0 {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // a direct vote overrides the vote cast by the delegate,
+    // so take back the weight the delegate voted with on behalf of the voter
+    if let Some(delegated) = DELEGATED_VOTES.may_load(deps.storage, (voter, proposal_id))? {
+        let mut ballot = BALLOTS.load(deps.storage, (proposal_id, &delegated.delegate))?;
+        ballot.weight -= delegated.weight;
+        remove_vote(&mut prop.votes, ballot.vote, delegated.weight);
+        BALLOTS.save(deps.storage, (proposal_id, &delegated.delegate), &ballot)?;
+        DELEGATED_VOTES.remove(deps.storage, (voter, proposal_id));
+
+        // the status may have been reached with the weight taken back,
+        // so it is computed again from the tallies below
+        if prop.status != Status::Open {
+            prop.status = Status::Open;
+            GRACE_ENDS.remove(deps.storage, proposal_id);
+        }
+    }
 
     // cast vote if no vote previously cast
-    BALLOTS.update(deps.storage, (proposal_id, &info.sender), |bal| match bal {
+    BALLOTS.update(deps.storage, (proposal_id, voter), |bal| match bal {
         Some(_) => Err(ContractError::AlreadyVoted {}),
         None => Ok(Ballot {
             weight: vote_power,
             vote,
         }),
     })?;
+    record_ballot(deps.storage, voter, proposal_id)?;
+    if let Some(rationale) = rationale {
+        RATIONALES.save(deps.storage, (proposal_id, voter), &rationale)?;
+    }
 
     // update vote tally
     prop.votes.add_vote(vote, vote_power);
-    prop.update_status(&env.block);
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    let status = update_status(deps.storage, proposal_id, &mut prop, &env.block)?;
+    proposals().save(deps.storage, proposal_id, &prop)?;
+    record_turnout(deps.storage, proposal_id, &prop)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "vote")
-        .add_attribute("sender", info.sender)
-        .add_attribute("proposal_id", proposal_id.to_string())
-        .add_attribute("status", format!("{:?}", prop.status)))
+    start_grace_period(deps.storage, proposal_id, &prop, &env.block)?;
+
+    Ok(status)
+}
+
+/// Stores the status reached by the votes and returns it. Proposals that can no longer pass
+/// are reported as rejected, but stay stored as open until someone closes them
+fn update_status(
+    storage: &dyn Storage,
+    proposal_id: u64,
+    prop: &mut Proposal,
+    block: &BlockInfo,
+) -> StdResult<Status> {
+    let status = proposal_status(storage, proposal_id, prop, block)?;
+    if status != Status::Rejected {
+        prop.status = status;
+    }
+    Ok(status)
+}
+
+/// Passed proposals can be executed once this is expired,
+/// proposals that only passed on expiration have their grace period start then
+fn grace_ends(
+    storage: &dyn Storage,
+    proposal_id: u64,
+    prop: &Proposal,
+    grace_period: Duration,
+) -> StdResult<Expiration> {
+    match GRACE_ENDS.may_load(storage, proposal_id)? {
+        Some(ends) => Ok(ends),
+        None => prop.expires + grace_period,
+    }
+}
+
+/// The grace period starts once the votes pass the proposal,
+/// or once the minimum voting period is over if that is later
+fn start_grace_period(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    prop: &Proposal,
+    block: &BlockInfo,
+) -> StdResult<()> {
+    if let Some(grace_period) = CONFIG.load(storage)?.grace_period {
+        if prop.current_status(block) == Status::Passed && !GRACE_ENDS.has(storage, proposal_id) {
+            let mut ends = grace_period.after(block);
+            if let Some(min_voting_end) = MIN_VOTING_ENDS.may_load(storage, proposal_id)? {
+                let after_min_voting = (min_voting_end + grace_period)?;
+                if after_min_voting > ends {
+                    ends = after_min_voting;
+                }
+            }
+            GRACE_ENDS.save(storage, proposal_id, &ends)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn execute_submit_signed_votes(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    votes: Vec<SignedVote>,
+) -> Result<Response, ContractError> {
+    let mut res = Response::new()
+        .add_attribute("action", "submit_signed_votes")
+        .add_attribute("sender", info.sender);
+
+    for signed in votes {
+        let voter = verify_signed_vote(deps.branch(), &env, &signed)?;
+        let SignedVotePayload {
+            proposal_id, vote, ..
+        } = signed.payload;
+        cast_vote(deps.branch(), &env, &voter, proposal_id, vote, None)?;
+        res = res.add_attribute("voter", voter);
+    }
+
+    Ok(res)
+}
+
+/// Checks the signature and nonce of a signed vote and returns the address of the signer
+fn verify_signed_vote(
+    deps: DepsMut,
+    env: &Env,
+    signed: &SignedVote,
+) -> Result<Addr, ContractError> {
+    if signed.payload.contract != env.contract.address {
+        return Err(ContractError::WrongContract {});
+    }
+
+    let hash = Sha256::digest(to_vec(&signed.payload)?);
+    let valid = deps
+        .api
+        .secp256k1_verify(&hash, &signed.signature, &signed.pubkey)
+        .map_err(StdError::from)?;
+    if !valid {
+        return Err(ContractError::InvalidSignature {});
+    }
+
+    let voter = pubkey_to_addr(deps.api, &signed.pubkey)?;
+
+    // each signed vote can only be submitted once
+    let nonce = NONCES.may_load(deps.storage, &voter)?.unwrap_or_default();
+    if signed.payload.nonce != nonce {
+        return Err(ContractError::InvalidNonce { expected: nonce });
+    }
+    NONCES.save(deps.storage, &voter, &(nonce + 1))?;
+
+    Ok(voter)
+}
+
+/// Derives the account address of a compressed secp256k1 public key,
+/// the same way the chain does: ripemd160(sha256(pubkey))
+fn pubkey_to_addr(api: &dyn Api, pubkey: &[u8]) -> Result<Addr, ContractError> {
+    if pubkey.len() != 33 {
+        return Err(ContractError::InvalidPubkey {});
+    }
+    let hash = Ripemd160::digest(Sha256::digest(pubkey));
+    Ok(api.addr_humanize(&CanonicalAddr::from(hash.to_vec()))?)
+}
+
+/// Indexes the ballot by voter and counts the proposal as voted on by them
+fn record_ballot(storage: &mut dyn Storage, voter: &Addr, proposal_id: u64) -> StdResult<()> {
+    VOTER_BALLOTS.save(storage, (voter, proposal_id), &Empty {})?;
+    PROPOSALS_VOTED.update(storage, voter, |count| -> StdResult<_> {
+        Ok(count.unwrap_or_default() + 1)
+    })?;
+    Ok(())
+}
+
+/// Sums the weight delegated to `delegate` at `height` by delegators that have not voted
+/// directly on the proposal. The weight is recorded per delegator so it can be taken back
+/// if they vote later.
+fn cast_delegated_power(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    group: &Cw4Contract,
+    proposal_id: u64,
+    delegate: &Addr,
+    height: u64,
+) -> StdResult<u64> {
+    let delegators = DELEGATORS
+        .may_load_at_height(storage, delegate, height)?
+        .unwrap_or_default();
+
+    let mut delegated_power = 0;
+    for delegator in delegators {
+        if BALLOTS.has(storage, (proposal_id, &delegator)) {
+            continue;
+        }
+        let weight = group
+            .is_voting_member(querier, &delegator, height)?
+            .unwrap_or_default();
+        if weight == 0 {
+            continue;
+        }
+        DELEGATED_VOTES.save(
+            storage,
+            (&delegator, proposal_id),
+            &DelegatedVote {
+                delegate: delegate.clone(),
+                weight,
+            },
+        )?;
+        delegated_power += weight;
+    }
+
+    Ok(delegated_power)
+}
+
+fn remove_vote(votes: &mut Votes, vote: Vote, weight: u64) {
+    match vote {
+        Vote::Yes => votes.yes -= weight,
+        Vote::Abstain => votes.abstain -= weight,
+        Vote::No => votes.no -= weight,
+        Vote::Veto => votes.veto -= weight,
+    }
 }
 
 pub fn execute_execute(
@@ -227,10 +729,11 @@ pub fn execute_execute(
     info: MessageInfo,
     proposal_id: u64,
 ) -> Result<Response, ContractError> {
-    let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    ensure_not_dissolved(deps.storage)?;
+    let mut prop = proposals().load(deps.storage, proposal_id)?;
     // we allow execution even after the proposal "expiration" as long as all vote come in before
     // that point. If it was approved on time, it can be executed any time.
-    prop.update_status(&env.block);
+    prop.status = proposal_status(deps.storage, proposal_id, &prop, &env.block)?;
     if prop.status != Status::Passed {
         return Err(ContractError::WrongExecuteStatus {});
     }
@@ -238,41 +741,243 @@ pub fn execute_execute(
     let cfg = CONFIG.load(deps.storage)?;
     cfg.authorize(deps.storage, &deps.querier, &info.sender)?;
 
+    // give members the chance to ragequit before a passed proposal is executed
+    if let Some(grace_period) = cfg.grace_period {
+        let ends = grace_ends(deps.storage, proposal_id, &prop, grace_period)?;
+        if !ends.is_expired(&env.block) {
+            return Err(ContractError::GracePeriod { ends });
+        }
+    }
+
     // set it to executed
     prop.status = Status::Executed;
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    proposals().save(deps.storage, proposal_id, &prop)?;
 
-    // dispatch all proposed messages
-    Ok(Response::new()
-        .add_messages(prop.msgs)
+    let msgs = proposal_msgs(&prop, &env.contract.address)?;
+    let res = match EXECUTION_MODES.may_load(deps.storage, proposal_id)? {
+        // every message replies, so failures are recorded instead of reverting the execution
+        Some(ExecutionMode::BestEffort) => {
+            Response::new().add_submessages(msgs.into_iter().enumerate().map(|(i, msg)| {
+                SubMsg::reply_always(msg, execution_reply_id(proposal_id, i as u32))
+            }))
+        }
+        _ => {
+            // NFTs and funds leaving the treasury are removed from the inventory
+            for msg in &msgs {
+                untrack_nft(deps.storage, msg)?;
+                untrack_funds(deps.storage, msg, &env.contract.address)?;
+            }
+            // dispatch all proposed messages
+            Response::new().add_messages(msgs)
+        }
+    };
+
+    Ok(res
         .add_attribute("action", "execute")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
+fn validate_action(api: &dyn Api, action: &ProposalAction) -> Result<(), ContractError> {
+    match action {
+        ProposalAction::SendTokens {
+            recipient,
+            denom,
+            amount,
+        } => {
+            api.addr_validate(recipient)?;
+            validate_denom(api, denom.clone())?;
+            if amount.is_zero() {
+                return Err(ContractError::InvalidAction {});
+            }
+        }
+        ProposalAction::TransferNft {
+            collection,
+            recipient,
+            ..
+        } => {
+            api.addr_validate(collection)?;
+            api.addr_validate(recipient)?;
+        }
+        ProposalAction::InstantiateContract(_) | ProposalAction::UpdateMetadata { .. } => {}
+        ProposalAction::UpdateConfig {
+            threshold,
+            max_voting_period,
+            min_voting_period,
+            grace_period,
+            ..
+        } => {
+            threshold.validate(u64::MAX)?;
+            validate_periods(*max_voting_period, *min_voting_period, *grace_period)?;
+        }
+    }
+    Ok(())
+}
+
+fn action_msg(action: ProposalAction, dao: &Addr) -> StdResult<CosmosMsg> {
+    let execute_self = |msg: &ExecuteMsg| -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: dao.to_string(),
+            msg: to_binary(msg)?,
+            funds: vec![],
+        }
+        .into())
+    };
+    match action {
+        ProposalAction::SendTokens {
+            recipient,
+            denom,
+            amount,
+        } => transfer_msg(&denom, &Addr::unchecked(recipient), amount),
+        ProposalAction::TransferNft {
+            collection,
+            token_id,
+            recipient,
+        } => Ok(WasmMsg::Execute {
+            contract_addr: collection,
+            msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient,
+                token_id,
+            })?,
+            funds: vec![],
+        }
+        .into()),
+        ProposalAction::InstantiateContract(init) => Ok(init.into_wasm_msg(dao.clone()).into()),
+        ProposalAction::UpdateMetadata {
+            name,
+            description,
+            image,
+        } => execute_self(&ExecuteMsg::UpdateMetadata {
+            name,
+            description,
+            image,
+        }),
+        ProposalAction::UpdateConfig {
+            threshold,
+            max_voting_period,
+            min_voting_period,
+            grace_period,
+            executor,
+        } => execute_self(&ExecuteMsg::UpdateConfig {
+            threshold,
+            max_voting_period,
+            min_voting_period,
+            grace_period,
+            executor,
+        }),
+    }
+}
+
+/// Messages dispatched when the proposal is executed, its actions first
+fn proposal_msgs(prop: &Proposal, dao: &Addr) -> StdResult<Vec<CosmosMsg>> {
+    prop.actions
+        .iter()
+        .map(|action| action_msg(action.clone(), dao))
+        .chain(prop.msgs.iter().cloned().map(Ok))
+        .collect()
+}
+
+/// Removes the NFT from the inventory if the message transfers, sends or burns it
+fn untrack_nft(storage: &mut dyn Storage, msg: &CosmosMsg) -> StdResult<()> {
+    if let CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr, msg, ..
+    }) = msg
+    {
+        if let Ok(
+            Cw721ExecuteMsg::TransferNft { token_id, .. }
+            | Cw721ExecuteMsg::SendNft { token_id, .. }
+            | Cw721ExecuteMsg::Burn { token_id },
+        ) = from_binary(msg)
+        {
+            remove_nft(storage, &Addr::unchecked(contract_addr), &token_id)?;
+        }
+    }
+    Ok(())
+}
+
+/// Lowers the revenue baseline by native funds leaving the treasury with `msg`,
+/// so the next distribution only splits what was received since the last one
+fn untrack_funds(storage: &mut dyn Storage, msg: &CosmosMsg, treasury: &Addr) -> StdResult<()> {
+    let funds = match msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, amount }) if to_address != treasury => amount,
+        CosmosMsg::Bank(BankMsg::Burn { amount }) => amount,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            funds,
+            ..
+        }) if contract_addr != treasury => funds,
+        CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. }) => funds,
+        _ => return Ok(()),
+    };
+    for coin in funds {
+        if let Some(baseline) = REVENUE_BASELINES.may_load(storage, &coin.denom)? {
+            let baseline = baseline.saturating_sub(coin.amount);
+            REVENUE_BASELINES.save(storage, &coin.denom, &baseline)?;
+        }
+    }
+    Ok(())
+}
+
+/// Raises the revenue baseline of `denom` by funds received that are not revenue
+fn track_funds(storage: &mut dyn Storage, denom: &str, amount: Uint128) -> StdResult<()> {
+    if let Some(baseline) = REVENUE_BASELINES.may_load(storage, denom)? {
+        REVENUE_BASELINES.save(storage, denom, &(baseline + amount))?;
+    }
+    Ok(())
+}
+
+fn add_nft(storage: &mut dyn Storage, collection: &Addr, token_id: &str) -> StdResult<()> {
+    if NFTS.has(storage, (collection, token_id)) {
+        return Ok(());
+    }
+    NFTS.save(storage, (collection, token_id), &Empty {})?;
+    COLLECTIONS.update(storage, collection, |count| -> StdResult<_> {
+        Ok(count.unwrap_or_default() + 1)
+    })?;
+    Ok(())
+}
+
+fn remove_nft(storage: &mut dyn Storage, collection: &Addr, token_id: &str) -> StdResult<()> {
+    if !NFTS.has(storage, (collection, token_id)) {
+        return Ok(());
+    }
+    NFTS.remove(storage, (collection, token_id));
+
+    let count = COLLECTIONS.load(storage, collection)? - 1;
+    if count == 0 {
+        COLLECTIONS.remove(storage, collection);
+    } else {
+        COLLECTIONS.save(storage, collection, &count)?;
+    }
+    Ok(())
+}
+
 pub fn execute_close(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     proposal_id: u64,
-) -> Result<Response<Empty>, ContractError> {
+) -> Result<Response, ContractError> {
     // anyone can trigger this if the vote passed
 
-    let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    let mut prop = proposals().load(deps.storage, proposal_id)?;
     if [Status::Executed, Status::Rejected, Status::Passed].contains(&prop.status) {
         return Err(ContractError::WrongCloseStatus {});
     }
+    let status = proposal_status(deps.storage, proposal_id, &prop, &env.block)?;
     // Avoid closing of Passed due to expiration proposals
-    if prop.current_status(&env.block) == Status::Passed {
+    if status == Status::Passed {
         return Err(ContractError::WrongCloseStatus {});
     }
-    if !prop.expires.is_expired(&env.block) {
+    // Proposals that can no longer pass can be closed before they expire
+    if status != Status::Rejected {
         return Err(ContractError::NotExpired {});
     }
 
     // set it to failed
     prop.status = Status::Rejected;
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    proposals().save(deps.storage, proposal_id, &prop)?;
+    record_turnout(deps.storage, proposal_id, &prop)?;
 
     Ok(Response::new()
         .add_attribute("action", "close")
@@ -287,7 +992,7 @@ pub fn execute_update_metadata(
     name: String,
     description: String,
     image: String,
-) -> Result<Response<Empty>, ContractError> {
+) -> Result<Response, ContractError> {
     // metadata can only be updated via a proposal
     if info.sender != env.contract.address {
         return Err(ContractError::Unauthorized {});
@@ -303,70 +1008,1233 @@ pub fn execute_update_metadata(
             image,
             threshold: config.threshold,
             max_voting_period: config.max_voting_period,
+            min_voting_period: config.min_voting_period,
+            grace_period: config.grace_period,
             executor: config.executor,
         },
     )?;
 
-    Ok(Response::new().add_attribute("action", "update_metadata"))
+    Ok(Response::new()
+        .add_messages(register_msg(deps.storage)?)
+        .add_attribute("action", "update_metadata"))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
-    if msg.id != INIT_GROUP_REPLY_ID {
-        return Err(ContractError::InvalidReplyID {});
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    threshold: Threshold,
+    max_voting_period: Duration,
+    min_voting_period: Option<Duration>,
+    grace_period: Option<Duration>,
+    executor: Option<Executor>,
+) -> Result<Response, ContractError> {
+    // config can only be updated via a proposal
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
     }
 
-    let reply = parse_reply_instantiate_data(msg);
-    match reply {
-        Ok(res) => {
-            let group =
-                Cw4Contract(deps.api.addr_validate(&res.contract_address).map_err(|_| {
-                    ContractError::InvalidGroup {
-                        addr: res.contract_address.clone(),
-                    }
-                })?);
+    let total_weight = GROUP.load(deps.storage)?.total_weight(&deps.querier)?;
+    threshold.validate(total_weight)?;
+    validate_periods(max_voting_period, min_voting_period, grace_period)?;
 
-            GROUP.save(deps.storage, &group)?;
+    let config = CONFIG.load(deps.storage)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            threshold,
+            max_voting_period,
+            min_voting_period,
+            grace_period,
+            executor,
+            ..config
+        },
+    )?;
 
-            Ok(Response::default().add_attribute("action", "reply_on_success"))
-        }
-        Err(_) => Err(ContractError::ReplyOnSuccess {}),
-    }
+    Ok(Response::new().add_attribute("action", "update_config"))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Threshold {} => to_binary(&query_threshold(deps)?),
-        QueryMsg::Proposal { proposal_id } => to_binary(&query_proposal(deps, env, proposal_id)?),
-        QueryMsg::Vote { proposal_id, voter } => to_binary(&query_vote(deps, proposal_id, voter)?),
-        QueryMsg::ListProposals { start_after, limit } => {
-            to_binary(&list_proposals(deps, env, start_after, limit)?)
-        }
-        QueryMsg::ReverseProposals {
-            start_before,
-            limit,
-        } => to_binary(&reverse_proposals(deps, env, start_before, limit)?),
-        QueryMsg::ListVotes {
-            proposal_id,
-            start_after,
-            limit,
-        } => to_binary(&list_votes(deps, proposal_id, start_after, limit)?),
-        QueryMsg::Voter { address } => to_binary(&query_voter(deps, address)?),
-        QueryMsg::ListVoters { start_after, limit } => {
-            to_binary(&list_voters(deps, start_after, limit)?)
-        }
-        QueryMsg::Group {} => to_binary(&query_group(deps)?),
-        QueryMsg::Metadata {} => to_binary(&query_metadata(deps)?),
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    if delegate == info.sender {
+        return Err(ContractError::SelfDelegation {});
     }
-}
 
-fn query_group(deps: Deps) -> StdResult<GroupResponse> {
-    let group = GROUP.load(deps.storage)?;
-    Ok(GroupResponse { group })
-}
+    // only members can delegate their weight
+    GROUP
+        .load(deps.storage)?
+        .is_member(&deps.querier, &info.sender, None)?
+        .ok_or(ContractError::Unauthorized {})?;
 
-fn query_metadata(deps: Deps) -> StdResult<MetadataResponse> {
+    let height = env.block.height;
+    if let Some(previous) = DELEGATIONS.may_load(deps.storage, &info.sender)? {
+        remove_delegator(deps.storage, &previous, &info.sender, height)?;
+    }
+    DELEGATIONS.save(deps.storage, &info.sender, &delegate, height)?;
+    DELEGATORS.update(
+        deps.storage,
+        &delegate,
+        height,
+        |delegators| -> StdResult<_> {
+            let mut delegators = delegators.unwrap_or_default();
+            delegators.push(info.sender.clone());
+            Ok(delegators)
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "delegate")
+        .add_attribute("sender", info.sender)
+        .add_attribute("delegate", delegate))
+}
+
+pub fn execute_undelegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let delegate = DELEGATIONS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NoDelegation {})?;
+
+    let height = env.block.height;
+    DELEGATIONS.remove(deps.storage, &info.sender, height)?;
+    remove_delegator(deps.storage, &delegate, &info.sender, height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "undelegate")
+        .add_attribute("sender", info.sender)
+        .add_attribute("delegate", delegate))
+}
+
+fn remove_delegator(
+    storage: &mut dyn Storage,
+    delegate: &Addr,
+    delegator: &Addr,
+    height: u64,
+) -> StdResult<()> {
+    let mut delegators = DELEGATORS.may_load(storage, delegate)?.unwrap_or_default();
+    delegators.retain(|addr| addr != delegator);
+    if delegators.is_empty() {
+        DELEGATORS.remove(storage, delegate, height)
+    } else {
+        DELEGATORS.save(storage, delegate, &delegators, height)
+    }
+}
+
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    wrapper: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let collection = info.sender;
+    let Cw721ReceiveMsg {
+        sender, token_id, ..
+    } = wrapper;
+
+    add_nft(deps.storage, &collection, &token_id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "receive_nft")
+        .add_attribute("collection", collection)
+        .add_attribute("from", sender)
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let token = info.sender;
+    TOKENS.save(deps.storage, &token, &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "receive")
+        .add_attribute("token", token)
+        .add_attribute("from", wrapper.sender)
+        .add_attribute("amount", wrapper.amount))
+}
+
+pub fn execute_update_token_list(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    // the token list can only be updated via a proposal
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for token in to_add {
+        let addr = deps.api.addr_validate(&token)?;
+        // make sure balances of the token can be queried later on
+        deps.querier
+            .query_wasm_smart::<TokenInfoResponse>(&addr, &Cw20QueryMsg::TokenInfo {})
+            .map_err(|_| ContractError::InvalidToken { addr: token })?;
+        TOKENS.save(deps.storage, &addr, &Empty {})?;
+    }
+    for token in to_remove {
+        let addr = deps.api.addr_validate(&token)?;
+        TOKENS.remove(deps.storage, &addr);
+    }
+
+    Ok(Response::new().add_attribute("action", "update_token_list"))
+}
+
+fn native_treasury_balance(deps: Deps, treasury: &Addr, denom: &str) -> StdResult<Uint128> {
+    // bids escrowed in auctions are not part of the treasury
+    let balance = deps.querier.query_balance(treasury, denom)?.amount;
+    let escrowed = ESCROWED_BIDS.may_load(deps.storage, denom)?;
+    Ok(balance.saturating_sub(escrowed.unwrap_or_default()))
+}
+
+fn treasury_balance(deps: Deps, treasury: &Addr, denom: &Denom) -> StdResult<Uint128> {
+    match denom {
+        Denom::Native(denom) => native_treasury_balance(deps, treasury, denom),
+        Denom::Cw20(address) => {
+            let BalanceResponse { balance } = deps.querier.query_wasm_smart(
+                address,
+                &Cw20QueryMsg::Balance {
+                    address: treasury.to_string(),
+                },
+            )?;
+            Ok(balance)
+        }
+    }
+}
+
+/// Fails if the treasury does not hold `amount` of `denom` outside of escrowed bids
+fn ensure_treasury_covers(
+    deps: Deps,
+    treasury: &Addr,
+    denom: &Denom,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let available = treasury_balance(deps, treasury, denom)?;
+    if amount > available {
+        return Err(ContractError::InsufficientTreasury { available });
+    }
+    Ok(())
+}
+
+/// Message paying `amount` of `denom` from the treasury to `recipient`
+fn transfer_msg(denom: &Denom, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(match denom {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount.u128(), denom),
+        }
+        .into(),
+        Denom::Cw20(address) => WasmMsg::Execute {
+            contract_addr: address.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
+
+fn validate_denom(api: &dyn Api, denom: Denom) -> StdResult<Denom> {
+    Ok(match denom {
+        Denom::Cw20(address) => Denom::Cw20(api.addr_validate(address.as_str())?),
+        native => native,
+    })
+}
+
+pub fn execute_create_stream(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    mut stream: Stream,
+) -> Result<Response, ContractError> {
+    // streams can only be created via a proposal
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    stream.recipient = deps.api.addr_validate(stream.recipient.as_str())?;
+    stream.denom = validate_denom(deps.api, stream.denom)?;
+    let cliff = stream.cliff.unwrap_or(stream.start);
+    if stream.end <= stream.start
+        || stream.interval == 0
+        || stream.amount_per_interval.is_zero()
+        || cliff < stream.start
+        || cliff > stream.end
+    {
+        return Err(ContractError::InvalidStream {});
+    }
+
+    let id = next_stream_id(deps.storage)?;
+    STREAMS.save(deps.storage, id, &stream)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_stream")
+        .add_attribute("stream_id", id.to_string())
+        .add_attribute("recipient", stream.recipient))
+}
+
+pub fn execute_claim_stream(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    ensure_not_dissolved(deps.storage)?;
+    let mut stream = STREAMS.load(deps.storage, id)?;
+    if info.sender != stream.recipient {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let amount = stream.claimable(env.block.time);
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    ensure_treasury_covers(deps.as_ref(), &env.contract.address, &stream.denom, amount)?;
+    stream.claimed += amount;
+    STREAMS.save(deps.storage, id, &stream)?;
+    let msg = transfer_msg(&stream.denom, &stream.recipient, amount)?;
+    untrack_funds(deps.storage, &msg, &env.contract.address)?;
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "claim_stream")
+        .add_attribute("sender", info.sender)
+        .add_attribute("stream_id", id.to_string())
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_cancel_stream(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    // streams can only be cancelled via a proposal
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let stream = STREAMS.load(deps.storage, id)?;
+    STREAMS.remove(deps.storage, id);
+
+    // the recipient keeps what has vested, the rest never leaves the treasury
+    let amount = stream.claimable(env.block.time);
+    let mut res = Response::new()
+        .add_attribute("action", "cancel_stream")
+        .add_attribute("stream_id", id.to_string())
+        .add_attribute("paid_out", amount);
+    if !amount.is_zero() {
+        let msg = transfer_msg(&stream.denom, &stream.recipient, amount)?;
+        untrack_funds(deps.storage, &msg, &env.contract.address)?;
+        res = res.add_message(msg);
+    }
+    Ok(res)
+}
+
+pub fn execute_ragequit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: RagequitMsg,
+) -> Result<Response, ContractError> {
+    // the group has already taken the weight and NFTs of the member
+    let group = GROUP.load(deps.storage)?;
+    if info.sender != group.addr() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let member = deps.api.addr_validate(&msg.member)?;
+    if let Some(proposal_id) = pending_yes_vote(deps.as_ref(), &env.block, &member)? {
+        return Err(ContractError::PendingYesVote { proposal_id });
+    }
+    let total_weight = group.total_weight(&deps.querier)? + msg.weight;
+
+    let mut paid = HashSet::new();
+    let mut msgs = vec![];
+    for denom in msg.denoms {
+        let denom = validate_denom(deps.api, denom)?;
+        if !paid.insert(denom_key(&denom)) {
+            continue;
+        }
+        let balance = treasury_balance(deps.as_ref(), &env.contract.address, &denom)?;
+        let amount = balance.multiply_ratio(msg.weight, total_weight);
+        if !amount.is_zero() {
+            let msg = transfer_msg(&denom, &member, amount)?;
+            untrack_funds(deps.storage, &msg, &env.contract.address)?;
+            msgs.push(msg);
+        }
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "ragequit")
+        .add_attribute("member", member)
+        .add_attribute("weight", msg.weight.to_string()))
+}
+
+/// Returns a proposal still in its voting or grace period, where the weight of `member`
+/// counts as yes, either from their own ballot or through their delegate.
+/// Members cannot take their share of the treasury and leave such a proposal behind.
+fn pending_yes_vote(deps: Deps, block: &BlockInfo, member: &Addr) -> StdResult<Option<u64>> {
+    let grace_period = CONFIG.load(deps.storage)?.grace_period;
+
+    // only the proposals the member voted on, directly or through their delegate
+    let direct = VOTER_BALLOTS
+        .prefix(member)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|id| id.map(|id| (id, member.clone())));
+    let delegated = DELEGATED_VOTES
+        .prefix(member)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(id, delegated)| (id, delegated.delegate)));
+    for item in direct.chain(delegated) {
+        let (id, voter) = item?;
+        if BALLOTS.load(deps.storage, (id, &voter))?.vote != Vote::Yes {
+            continue;
+        }
+        let prop = proposals().load(deps.storage, id)?;
+        let pending = match proposal_status(deps.storage, id, &prop, block)? {
+            Status::Open => true,
+            // passed proposals can be executed, or stay passed if executing them fails,
+            // once the grace period is over
+            Status::Passed => match grace_period {
+                Some(grace_period) => {
+                    !grace_ends(deps.storage, id, &prop, grace_period)?.is_expired(block)
+                }
+                None => false,
+            },
+            _ => false,
+        };
+        if pending {
+            return Ok(Some(id));
+        }
+    }
+    Ok(None)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_start_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+    denom: String,
+    reserve_price: Uint128,
+    duration: Duration,
+) -> Result<Response, ContractError> {
+    // auctions can only be started via a proposal
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // the NFT leaves the inventory while on auction so it cannot be auctioned twice
+    let collection = deps.api.addr_validate(&collection)?;
+    if !NFTS.has(deps.storage, (&collection, &token_id)) {
+        return Err(ContractError::NftNotHeld {
+            collection: collection.into_string(),
+            token_id,
+        });
+    }
+    remove_nft(deps.storage, &collection, &token_id)?;
+
+    let id = next_auction_id(deps.storage)?;
+    AUCTIONED_NFTS.save(deps.storage, (&collection, &token_id), &id)?;
+    let auction = Auction {
+        collection,
+        token_id,
+        denom,
+        reserve_price,
+        end: duration.after(&env.block),
+        highest_bid: None,
+    };
+    AUCTIONS.save(deps.storage, id, &auction)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "start_auction")
+        .add_attribute("auction_id", id.to_string())
+        .add_attribute("collection", auction.collection)
+        .add_attribute("token_id", auction.token_id))
+}
+
+pub fn execute_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let mut auction = AUCTIONS.load(deps.storage, auction_id)?;
+    if auction.end.is_expired(&env.block) {
+        return Err(ContractError::AuctionEnded {});
+    }
+
+    let amount = must_pay(&info, &auction.denom)?;
+    let min = match &auction.highest_bid {
+        Some(bid) => bid.amount + Uint128::one(),
+        None => auction.reserve_price,
+    };
+    if amount < min {
+        return Err(ContractError::BidTooLow { min });
+    }
+
+    // refund the bid that was outbid
+    let mut res = Response::new();
+    let mut escrowed = ESCROWED_BIDS
+        .may_load(deps.storage, &auction.denom)?
+        .unwrap_or_default();
+    if let Some(outbid) = auction.highest_bid.take() {
+        escrowed -= outbid.amount;
+        res = res.add_message(BankMsg::Send {
+            to_address: outbid.bidder.into_string(),
+            amount: coins(outbid.amount.u128(), &auction.denom),
+        });
+    }
+    ESCROWED_BIDS.save(deps.storage, &auction.denom, &(escrowed + amount))?;
+
+    auction.highest_bid = Some(Bid {
+        bidder: info.sender.clone(),
+        amount,
+    });
+    AUCTIONS.save(deps.storage, auction_id, &auction)?;
+
+    Ok(res
+        .add_attribute("action", "bid")
+        .add_attribute("sender", info.sender)
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_settle_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let auction = AUCTIONS.load(deps.storage, auction_id)?;
+    if !auction.end.is_expired(&env.block) {
+        return Err(ContractError::AuctionNotEnded {});
+    }
+    AUCTIONS.remove(deps.storage, auction_id);
+    AUCTIONED_NFTS.remove(deps.storage, (&auction.collection, &auction.token_id));
+
+    let res = Response::new()
+        .add_attribute("action", "settle_auction")
+        .add_attribute("sender", info.sender)
+        .add_attribute("auction_id", auction_id.to_string());
+
+    match auction.highest_bid.clone() {
+        // the winning bid stays in the treasury
+        Some(bid) => {
+            ESCROWED_BIDS.update(deps.storage, &auction.denom, |escrowed| -> StdResult<_> {
+                Ok(escrowed.unwrap_or_default() - bid.amount)
+            })?;
+            let transfer = WasmMsg::Execute {
+                contract_addr: auction.collection.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: bid.bidder.to_string(),
+                    token_id: auction.token_id.clone(),
+                })?,
+                funds: vec![],
+            };
+            // the sale is not revenue to split with the group
+            track_funds(deps.storage, &auction.denom, bid.amount)?;
+            // a proposal may have moved the NFT during the auction,
+            // in which case the winner gets their bid back
+            SETTLING_AUCTION.save(deps.storage, &auction)?;
+            Ok(res
+                .add_submessage(SubMsg::reply_always(transfer, SETTLE_AUCTION_REPLY_ID))
+                .add_attribute("winner", bid.bidder)
+                .add_attribute("amount", bid.amount))
+        }
+        None => {
+            restore_auctioned_nft(deps, &env, &auction)?;
+            Ok(res)
+        }
+    }
+}
+
+pub fn execute_cancel_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    // auctions can only be cancelled via a proposal
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let auction = AUCTIONS.load(deps.storage, auction_id)?;
+    AUCTIONS.remove(deps.storage, auction_id);
+    AUCTIONED_NFTS.remove(deps.storage, (&auction.collection, &auction.token_id));
+
+    let mut res = Response::new();
+    if let Some(bid) = &auction.highest_bid {
+        ESCROWED_BIDS.update(deps.storage, &auction.denom, |escrowed| -> StdResult<_> {
+            Ok(escrowed.unwrap_or_default() - bid.amount)
+        })?;
+        res = res.add_message(BankMsg::Send {
+            to_address: bid.bidder.to_string(),
+            amount: coins(bid.amount.u128(), &auction.denom),
+        });
+    }
+    restore_auctioned_nft(deps, &env, &auction)?;
+
+    Ok(res
+        .add_attribute("action", "cancel_auction")
+        .add_attribute("auction_id", auction_id.to_string()))
+}
+
+/// Takes the NFT of an auction that did not sell back into the inventory,
+/// unless it left the treasury during the auction
+fn restore_auctioned_nft(deps: DepsMut, env: &Env, auction: &Auction) -> StdResult<()> {
+    let owner: StdResult<OwnerOfResponse> = deps.querier.query_wasm_smart(
+        &auction.collection,
+        &Cw721QueryMsg::OwnerOf {
+            token_id: auction.token_id.clone(),
+            include_expired: None,
+        },
+    );
+    if matches!(owner, Ok(res) if res.owner == env.contract.address) {
+        add_nft(deps.storage, &auction.collection, &auction.token_id)?;
+    }
+    Ok(())
+}
+
+/// Distribution message of an sg-nft-group
+#[cw_serde]
+enum GroupExecuteMsg {
+    Distribute {},
+}
+
+/// Funds of `denom` received since the last distribution, and the current balance
+fn revenue(deps: Deps, env: &Env, denom: &str) -> StdResult<(Uint128, Uint128)> {
+    let balance = native_treasury_balance(deps, &env.contract.address, denom)?;
+    let baseline = REVENUE_BASELINES.may_load(deps.storage, denom)?;
+    Ok((
+        balance.saturating_sub(baseline.unwrap_or_default()),
+        balance,
+    ))
+}
+
+pub fn execute_update_revenue_split(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    split: Option<RevenueSplit>,
+) -> Result<Response, ContractError> {
+    // the revenue split can only be changed via a proposal
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // only funds received from now on are split
+    REVENUE_BASELINES.clear(deps.storage);
+    match split {
+        Some(split) => {
+            if split.group_share > Decimal::one() {
+                return Err(ContractError::InvalidRevenueSplit {});
+            }
+            for denom in &split.denoms {
+                let balance = native_treasury_balance(deps.as_ref(), &env.contract.address, denom)?;
+                REVENUE_BASELINES.save(deps.storage, denom, &balance)?;
+            }
+            REVENUE_SPLIT.save(deps.storage, &split)?;
+        }
+        None => REVENUE_SPLIT.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_revenue_split"))
+}
+
+pub fn execute_distribute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let split = REVENUE_SPLIT
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoRevenueSplit {})?;
+    let group = GROUP.load(deps.storage)?.addr();
+
+    // only the group share is sent along, the rest of the treasury stays
+    let mut shares = vec![];
+    for denom in &split.denoms {
+        let (revenue, balance) = revenue(deps.as_ref(), &env, denom)?;
+        let amount = revenue * split.group_share;
+        REVENUE_BASELINES.save(deps.storage, denom, &(balance - amount))?;
+        if !amount.is_zero() {
+            shares.push(coin(amount.u128(), denom));
+        }
+    }
+    if shares.is_empty() {
+        return Err(ContractError::NothingToDistribute {});
+    }
+    let msg = WasmMsg::Execute {
+        contract_addr: group.to_string(),
+        msg: to_binary(&GroupExecuteMsg::Distribute {})?,
+        funds: shares,
+    };
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "distribute")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_set_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grantee: String,
+    denom: Denom,
+    cap: Uint128,
+    period: Duration,
+) -> Result<Response, ContractError> {
+    // allowances can only be granted via a proposal
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    if cap.is_zero() || matches!(period, Duration::Height(0) | Duration::Time(0)) {
+        return Err(ContractError::InvalidAllowance {});
+    }
+
+    let grantee = deps.api.addr_validate(&grantee)?;
+    let denom = validate_denom(deps.api, denom)?;
+    let key = denom_key(&denom);
+
+    // keep what was spent in the current period when only changing the cap,
+    // a lowered cap counts as fully spent until the period resets
+    let (spent, resets) = match ALLOWANCES.may_load(deps.storage, (&grantee, &key))? {
+        Some(allowance) if allowance.period == period => {
+            (allowance.spent.min(cap), allowance.resets)
+        }
+        _ => (Uint128::zero(), period.after(&env.block)),
+    };
+    ALLOWANCES.save(
+        deps.storage,
+        (&grantee, &key),
+        &Allowance {
+            denom,
+            cap,
+            period,
+            spent,
+            resets,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_allowance")
+        .add_attribute("grantee", grantee)
+        .add_attribute("denom", key)
+        .add_attribute("cap", cap))
+}
+
+pub fn execute_revoke_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grantee: String,
+    denom: Denom,
+) -> Result<Response, ContractError> {
+    // allowances can only be revoked via a proposal
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let grantee = deps.api.addr_validate(&grantee)?;
+    let key = denom_key(&validate_denom(deps.api, denom)?);
+    ALLOWANCES.remove(deps.storage, (&grantee, &key));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_allowance")
+        .add_attribute("grantee", grantee)
+        .add_attribute("denom", key))
+}
+
+pub fn execute_spend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    to: String,
+    denom: Denom,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ensure_not_dissolved(deps.storage)?;
+    let to = deps.api.addr_validate(&to)?;
+    let key = denom_key(&validate_denom(deps.api, denom)?);
+    let mut allowance = ALLOWANCES
+        .may_load(deps.storage, (&info.sender, &key))?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    allowance.refresh(&env.block);
+    let remaining = allowance.cap.saturating_sub(allowance.spent);
+    if amount > remaining {
+        return Err(ContractError::AllowanceExceeded { remaining });
+    }
+    ensure_treasury_covers(
+        deps.as_ref(),
+        &env.contract.address,
+        &allowance.denom,
+        amount,
+    )?;
+    allowance.spent += amount;
+    ALLOWANCES.save(deps.storage, (&info.sender, &key), &allowance)?;
+    let msg = transfer_msg(&allowance.denom, &to, amount)?;
+    untrack_funds(deps.storage, &msg, &env.contract.address)?;
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "spend")
+        .add_attribute("sender", info.sender)
+        .add_attribute("to", to)
+        .add_attribute("denom", key)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: String,
+    offer: Asset,
+    min_receive: Uint128,
+) -> Result<Response, ContractError> {
+    // treasury funds can only be swapped via a proposal
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pair = PairContract(deps.api.addr_validate(&pair)?);
+    let offered = match &offer.info {
+        AssetInfo::NativeToken { denom } => coin(offer.amount.u128(), denom),
+        AssetInfo::Token { .. } => coin(0, ""),
+    };
+    let msg = pair.swap_msg(offer, min_receive, None)?;
+    untrack_funds(deps.storage, &msg, &env.contract.address)?;
+
+    // the proceeds are not revenue, so the balances of the split denoms
+    // are compared once the swap is done
+    let mut balances = vec![];
+    if let Some(split) = REVENUE_SPLIT.may_load(deps.storage)? {
+        for denom in split.denoms {
+            let mut balance =
+                native_treasury_balance(deps.as_ref(), &env.contract.address, &denom)?;
+            if denom == offered.denom {
+                balance = balance.saturating_sub(offered.amount);
+            }
+            balances.push(coin(balance.u128(), denom));
+        }
+    }
+    SWAP_BALANCES.save(deps.storage, &balances)?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(msg, SWAP_REPLY_ID))
+        .add_attribute("action", "swap")
+        .add_attribute("pair", pair.0)
+        .add_attribute("min_receive", min_receive))
+}
+
+pub fn execute_create_sub_dao(
+    env: Env,
+    info: MessageInfo,
+    init: ContractInstantiateMsg,
+) -> Result<Response, ContractError> {
+    // sub-DAOs can only be created via a proposal
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // the child is administered by this DAO and knows it as its parent
+    let mut msg: InstantiateMsg = from_binary(&init.msg)?;
+    msg.parent = Some(env.contract.address.to_string());
+    let init = ContractInstantiateMsg {
+        msg: to_binary(&msg)?,
+        admin: Some(Admin::Creator {}),
+        ..init
+    };
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(
+            init.into_wasm_msg(env.contract.address),
+            INIT_SUB_DAO_REPLY_ID,
+        ))
+        .add_attribute("action", "create_sub_dao"))
+}
+
+fn load_sub_dao(deps: Deps, addr: &str) -> Result<Addr, ContractError> {
+    let addr = deps.api.addr_validate(addr)?;
+    if !SUB_DAOS.has(deps.storage, &addr) {
+        return Err(ContractError::NotSubDao {
+            addr: addr.into_string(),
+        });
+    }
+    Ok(addr)
+}
+
+pub fn execute_override_sub_dao(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    addr: String,
+    msgs: Vec<CosmosMsg>,
+) -> Result<Response, ContractError> {
+    // sub-DAOs can only be overridden via a proposal
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let sub_dao = load_sub_dao(deps.as_ref(), &addr)?;
+    let msg = WasmMsg::Execute {
+        contract_addr: sub_dao.to_string(),
+        msg: to_binary(&ExecuteMsg::ParentExecute { msgs })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "override_sub_dao")
+        .add_attribute("sub_dao", sub_dao))
+}
+
+pub fn execute_dissolve_sub_dao(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    // sub-DAOs can only be dissolved via a proposal
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let sub_dao = load_sub_dao(deps.as_ref(), &addr)?;
+    SUB_DAOS.remove(deps.storage, &sub_dao);
+
+    // revoke its budgets
+    let denoms = ALLOWANCES
+        .prefix(&sub_dao)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for denom in denoms {
+        ALLOWANCES.remove(deps.storage, (&sub_dao, &denom));
+    }
+
+    let msg = WasmMsg::Execute {
+        contract_addr: sub_dao.to_string(),
+        msg: to_binary(&ExecuteMsg::Dissolve {})?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "dissolve_sub_dao")
+        .add_attribute("sub_dao", sub_dao))
+}
+
+fn ensure_parent(deps: Deps, sender: &Addr) -> Result<Addr, ContractError> {
+    match PARENT.may_load(deps.storage)? {
+        Some(parent) if parent == *sender => Ok(parent),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+/// A dissolved DAO has handed its treasury back and cannot act anymore
+fn ensure_not_dissolved(storage: &dyn Storage) -> Result<(), ContractError> {
+    if DISSOLVED.may_load(storage)?.unwrap_or_default() {
+        return Err(ContractError::Dissolved {});
+    }
+    Ok(())
+}
+
+pub fn execute_parent_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msgs: Vec<CosmosMsg>,
+) -> Result<Response, ContractError> {
+    ensure_parent(deps.as_ref(), &info.sender)?;
+
+    // NFTs and funds leaving the treasury are removed from the inventory
+    for msg in &msgs {
+        untrack_nft(deps.storage, msg)?;
+        untrack_funds(deps.storage, msg, &env.contract.address)?;
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "parent_execute"))
+}
+
+pub fn execute_dissolve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let parent = ensure_parent(deps.as_ref(), &info.sender)?;
+    DISSOLVED.save(deps.storage, &true)?;
+
+    let mut msgs = vec![];
+    for coin in deps.querier.query_all_balances(&env.contract.address)? {
+        let denom = Denom::Native(coin.denom);
+        let amount = treasury_balance(deps.as_ref(), &env.contract.address, &denom)?;
+        if !amount.is_zero() {
+            msgs.push(transfer_msg(&denom, &parent, amount)?);
+        }
+    }
+    // any cw20 can register itself by sending tokens to the treasury,
+    // so tokens failing to report or transfer their balance are left behind
+    let mut sweeps = vec![];
+    for token in TOKENS.keys(deps.storage, None, None, Order::Ascending) {
+        let denom = Denom::Cw20(token?);
+        if let Ok(amount) = treasury_balance(deps.as_ref(), &env.contract.address, &denom) {
+            if !amount.is_zero() {
+                sweeps.push(SubMsg::reply_on_error(
+                    transfer_msg(&denom, &parent, amount)?,
+                    SWEEP_CW20_REPLY_ID,
+                ));
+            }
+        }
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_submessages(sweeps)
+        .add_messages(deregister_msg(deps.storage)?)
+        .add_attribute("action", "dissolve")
+        .add_attribute("parent", parent))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        INIT_GROUP_REPLY_ID => group_reply(deps, msg),
+        INIT_SUB_DAO_REPLY_ID => sub_dao_reply(deps, msg),
+        // a failed cw20 transfer does not stop the DAO from being dissolved
+        SWEEP_CW20_REPLY_ID => Ok(Response::new().add_attribute("action", "sweep_cw20_failed")),
+        SETTLE_AUCTION_REPLY_ID => settle_auction_reply(deps, env, msg),
+        SWAP_REPLY_ID => swap_reply(deps, env),
+        _ => execution_reply(deps, env, msg),
+    }
+}
+
+fn settle_auction_reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let auction = SETTLING_AUCTION.load(deps.storage)?;
+    SETTLING_AUCTION.remove(deps.storage);
+
+    match (msg.result, auction.highest_bid) {
+        (SubMsgResult::Err(err), Some(bid)) => {
+            let refund = BankMsg::Send {
+                to_address: bid.bidder.to_string(),
+                amount: coins(bid.amount.u128(), &auction.denom),
+            }
+            .into();
+            untrack_funds(deps.storage, &refund, &env.contract.address)?;
+            Ok(Response::new()
+                .add_message(refund)
+                .add_attribute("action", "settle_auction_refund")
+                .add_attribute("bidder", bid.bidder)
+                .add_attribute("amount", bid.amount)
+                .add_attribute("error", err))
+        }
+        _ => Ok(Response::new()),
+    }
+}
+
+/// Raises the revenue baselines by the proceeds of a swap
+fn swap_reply(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let balances = SWAP_BALANCES.load(deps.storage)?;
+    SWAP_BALANCES.remove(deps.storage);
+
+    for before in balances {
+        let after = native_treasury_balance(deps.as_ref(), &env.contract.address, &before.denom)?;
+        track_funds(
+            deps.storage,
+            &before.denom,
+            after.saturating_sub(before.amount),
+        )?;
+    }
+    Ok(Response::new())
+}
+
+fn group_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let reply = parse_reply_instantiate_data(msg);
+    match reply {
+        Ok(res) => {
+            let group =
+                Cw4Contract(deps.api.addr_validate(&res.contract_address).map_err(|_| {
+                    ContractError::InvalidGroup {
+                        addr: res.contract_address.clone(),
+                    }
+                })?);
+
+            GROUP.save(deps.storage, &group)?;
+
+            // the registry needs the group to list the DAO
+            Ok(Response::default()
+                .add_messages(register_msg(deps.storage)?)
+                .add_attribute("action", "reply_on_success"))
+        }
+        Err(_) => Err(ContractError::ReplyOnSuccess {}),
+    }
+}
+
+fn sub_dao_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let res = parse_reply_instantiate_data(msg).map_err(|_| ContractError::ReplyOnSuccess {})?;
+    let sub_dao = deps.api.addr_validate(&res.contract_address)?;
+    SUB_DAOS.save(deps.storage, &sub_dao, &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "sub_dao_created")
+        .add_attribute("sub_dao", sub_dao))
+}
+
+/// Records the result of a message of a proposal executed in best effort mode
+fn execution_reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let (proposal_id, index) = parse_execution_reply_id(msg.id);
+    let prop = proposals()
+        .may_load(deps.storage, proposal_id)?
+        .filter(|prop| prop.status == Status::Executed)
+        .ok_or(ContractError::InvalidReplyID {})?;
+    let msgs = proposal_msgs(&prop, &env.contract.address)?;
+    let proposal_msg = msgs
+        .get(index as usize)
+        .ok_or(ContractError::InvalidReplyID {})?;
+
+    let result = match msg.result {
+        SubMsgResult::Ok(_) => {
+            // NFTs and funds leaving the treasury are removed from the inventory
+            untrack_nft(deps.storage, proposal_msg)?;
+            untrack_funds(deps.storage, proposal_msg, &env.contract.address)?;
+            MessageResult::Success {}
+        }
+        SubMsgResult::Err(error) => MessageResult::Failure { error },
+    };
+    EXECUTION_RESULTS.save(deps.storage, (proposal_id, index), &result)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execution_reply")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("index", index.to_string())
+        .add_attribute(
+            "success",
+            matches!(result, MessageResult::Success {}).to_string(),
+        ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Threshold {} => to_binary(&query_threshold(deps)?),
+        QueryMsg::Proposal { proposal_id } => to_binary(&query_proposal(deps, env, proposal_id)?),
+        QueryMsg::Vote { proposal_id, voter } => to_binary(&query_vote(deps, proposal_id, voter)?),
+        QueryMsg::ListProposals {
+            start_after,
+            limit,
+            category,
+        } => to_binary(&list_proposals(deps, env, start_after, limit, category)?),
+        QueryMsg::ListProposalsFiltered {
+            status,
+            proposer,
+            execution_status,
+            start_after,
+            limit,
+        } => to_binary(&list_proposals_filtered(
+            deps,
+            env,
+            status,
+            proposer,
+            execution_status,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ReverseProposals {
+            start_before,
+            limit,
+        } => to_binary(&reverse_proposals(deps, env, start_before, limit)?),
+        QueryMsg::ListVotes {
+            proposal_id,
+            start_after,
+            limit,
+        } => to_binary(&list_votes(deps, proposal_id, start_after, limit)?),
+        QueryMsg::ListVotesByVoter {
+            voter,
+            start_after,
+            limit,
+        } => to_binary(&list_votes_by_voter(deps, voter, start_after, limit)?),
+        QueryMsg::ParticipationStats {} => to_binary(&query_participation_stats(deps)?),
+        QueryMsg::MemberParticipation { address } => {
+            to_binary(&query_member_participation(deps, address)?)
+        }
+        QueryMsg::Voter { address } => to_binary(&query_voter(deps, address)?),
+        QueryMsg::ListVoters { start_after, limit } => {
+            to_binary(&list_voters(deps, start_after, limit)?)
+        }
+        QueryMsg::Group {} => to_binary(&query_group(deps)?),
+        QueryMsg::Metadata {} => to_binary(&query_metadata(deps)?),
+        QueryMsg::Delegation { delegator } => to_binary(&query_delegation(deps, delegator)?),
+        QueryMsg::ListDelegations { start_after, limit } => {
+            to_binary(&list_delegations(deps, start_after, limit)?)
+        }
+        QueryMsg::VotingPower { address, at_height } => {
+            to_binary(&query_voting_power(deps, address, at_height)?)
+        }
+        QueryMsg::ListNfts {
+            collection,
+            start_after,
+            limit,
+        } => to_binary(&list_nfts(deps, collection, start_after, limit)?),
+        QueryMsg::ListCollections { start_after, limit } => {
+            to_binary(&list_collections(deps, start_after, limit)?)
+        }
+        QueryMsg::TreasuryBalances { start_after, limit } => {
+            to_binary(&query_treasury_balances(deps, env, start_after, limit)?)
+        }
+        QueryMsg::Stream { id } => to_binary(&query_stream(deps, env, id)?),
+        QueryMsg::ListStreams { start_after, limit } => {
+            to_binary(&list_streams(deps, env, start_after, limit)?)
+        }
+        QueryMsg::Allowance { grantee, denom } => {
+            to_binary(&query_allowance(deps, env, grantee, denom)?)
+        }
+        QueryMsg::ListAllowances { grantee } => to_binary(&list_allowances(deps, env, grantee)?),
+        QueryMsg::ListSubDaos { start_after, limit } => {
+            to_binary(&list_sub_daos(deps, env, start_after, limit)?)
+        }
+        QueryMsg::Parent {} => to_binary(&query_parent(deps)?),
+        QueryMsg::Auction { id } => to_binary(&query_auction(deps, id)?),
+        QueryMsg::ListAuctions { start_after, limit } => {
+            to_binary(&list_auctions(deps, start_after, limit)?)
+        }
+        QueryMsg::RevenueSplit {} => to_binary(&query_revenue_split(deps, env)?),
+        QueryMsg::SimulateProposal { proposal_id } => {
+            to_binary(&simulate_proposal(deps, env, proposal_id)?)
+        }
+        QueryMsg::ExecutionResults { proposal_id } => {
+            to_binary(&query_execution_results(deps, proposal_id)?)
+        }
+        QueryMsg::Nonce { address } => to_binary(&query_nonce(deps, address)?),
+    }
+}
+
+fn query_group(deps: Deps) -> StdResult<GroupResponse> {
+    let group = GROUP.load(deps.storage)?;
+    Ok(GroupResponse { group })
+}
+
+fn query_metadata(deps: Deps) -> StdResult<MetadataResponse> {
     let config = CONFIG.load(deps.storage)?;
     Ok(MetadataResponse {
         name: config.name,
@@ -383,18 +2251,8 @@ fn query_threshold(deps: Deps) -> StdResult<ThresholdResponse> {
 }
 
 fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<ProposalResponse> {
-    let prop = PROPOSALS.load(deps.storage, id)?;
-    let status = prop.current_status(&env.block);
-    let threshold = prop.threshold.to_response(prop.total_weight);
-    Ok(ProposalResponse {
-        id,
-        title: prop.title,
-        description: prop.description,
-        msgs: prop.msgs,
-        status,
-        expires: prop.expires,
-        threshold,
-    })
+    let prop = proposals().load(deps.storage, id)?;
+    map_proposal(deps.storage, &env.block, Ok((id, prop)))
 }
 
 // settings for pagination
@@ -406,16 +2264,23 @@ fn list_proposals(
     env: Env,
     start_after: Option<u64>,
     limit: Option<u32>,
+    category: Option<ProposalCategory>,
 ) -> StdResult<ProposalListResponse> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let start = start_after.map(Bound::exclusive);
-    let proposals = PROPOSALS
+    let props = proposals()
         .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| match (&category, item) {
+            (Some(category), Ok((_, prop))) => {
+                matches!(&prop.metadata, Some(metadata) if &metadata.category == category)
+            }
+            _ => true,
+        })
         .take(limit)
-        .map(|p| map_proposal(&env.block, p))
+        .map(|p| map_proposal(deps.storage, &env.block, p))
         .collect::<StdResult<_>>()?;
 
-    Ok(ProposalListResponse { proposals })
+    Ok(ProposalListResponse { proposals: props })
 }
 
 fn reverse_proposals(
@@ -426,42 +2291,136 @@ fn reverse_proposals(
 ) -> StdResult<ProposalListResponse> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let end = start_before.map(Bound::exclusive);
-    let props: StdResult<Vec<_>> = PROPOSALS
+    let props: StdResult<Vec<_>> = proposals()
         .range(deps.storage, None, end, Order::Descending)
         .take(limit)
-        .map(|p| map_proposal(&env.block, p))
+        .map(|p| map_proposal(deps.storage, &env.block, p))
         .collect();
 
     Ok(ProposalListResponse { proposals: props? })
 }
 
 fn map_proposal(
+    storage: &dyn Storage,
     block: &BlockInfo,
     item: StdResult<(u64, Proposal)>,
 ) -> StdResult<ProposalResponse> {
-    item.map(|(id, prop)| {
-        let status = prop.current_status(block);
-        let threshold = prop.threshold.to_response(prop.total_weight);
-        ProposalResponse {
-            id,
-            title: prop.title,
-            description: prop.description,
-            msgs: prop.msgs,
-            status,
-            expires: prop.expires,
-            threshold,
-        }
+    let (id, prop) = item?;
+    let status = proposal_status(storage, id, &prop, block)?;
+    proposal_response(storage, id, prop, status)
+}
+
+fn proposal_response(
+    storage: &dyn Storage,
+    id: u64,
+    prop: Proposal,
+    status: Status,
+) -> StdResult<ProposalResponse> {
+    let threshold = prop.threshold.to_response(prop.total_weight);
+    let execution_status = execution_status(&execution_results(storage, id)?);
+    Ok(ProposalResponse {
+        id,
+        proposer: prop.proposer,
+        title: prop.title,
+        description: prop.description,
+        msgs: prop.msgs,
+        actions: prop
+            .actions
+            .into_iter()
+            .map(|action| ActionResponse {
+                summary: action.summary(),
+                action,
+            })
+            .collect(),
+        metadata: prop.metadata,
+        status,
+        execution_status,
+        expires: prop.expires,
+        threshold,
     })
 }
 
+/// Stored statuses of proposals that can currently have `status`,
+/// as open proposals are only updated when voted on, executed or closed
+fn stored_statuses(status: Status) -> Vec<Status> {
+    match status {
+        Status::Passed => vec![Status::Open, Status::Passed],
+        Status::Rejected => vec![Status::Open, Status::Rejected],
+        status => vec![status],
+    }
+}
+
+fn list_proposals_filtered(
+    deps: Deps,
+    env: Env,
+    status: Option<Status>,
+    proposer: Option<String>,
+    execution_status: Option<ExecutionStatus>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ProposalListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let proposer = maybe_addr(deps.api, proposer)?;
+    let start = || start_after.map(Bound::exclusive);
+
+    type Range<'a> = Box<dyn Iterator<Item = StdResult<(u64, Proposal)>> + 'a>;
+    let ranges: Vec<Range> = match (proposer, status) {
+        (Some(proposer), _) => vec![proposals().idx.proposer.prefix(proposer).range(
+            deps.storage,
+            start(),
+            None,
+            Order::Ascending,
+        )],
+        (None, Some(status)) => stored_statuses(status)
+            .into_iter()
+            .map(|stored| {
+                proposals().idx.status.prefix(stored as u8).range(
+                    deps.storage,
+                    start(),
+                    None,
+                    Order::Ascending,
+                )
+            })
+            .collect(),
+        (None, None) => vec![proposals().range(deps.storage, start(), None, Order::Ascending)],
+    };
+
+    // the first matches of every range, of which the lowest ids are returned
+    let mut props = vec![];
+    for range in ranges {
+        let mut matched = 0;
+        for item in range {
+            if matched == limit {
+                break;
+            }
+            let (id, prop) = item?;
+            let current = proposal_status(deps.storage, id, &prop, &env.block)?;
+            if status.is_some() && status != Some(current) {
+                continue;
+            }
+            let prop = proposal_response(deps.storage, id, prop, current)?;
+            if execution_status.is_none() || prop.execution_status == execution_status {
+                props.push(prop);
+                matched += 1;
+            }
+        }
+    }
+    props.sort_by_key(|prop| prop.id);
+    props.truncate(limit);
+
+    Ok(ProposalListResponse { proposals: props })
+}
+
 fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<VoteResponse> {
     let voter_addr = deps.api.addr_validate(&voter)?;
     let prop = BALLOTS.may_load(deps.storage, (proposal_id, &voter_addr))?;
+    let rationale = RATIONALES.may_load(deps.storage, (proposal_id, &voter_addr))?;
     let vote = prop.map(|b| VoteInfo {
         proposal_id,
         voter,
         vote: b.vote,
         weight: b.weight,
+        rationale,
     });
     Ok(VoteResponse { vote })
 }
@@ -481,11 +2440,14 @@ fn list_votes(
         .range(deps.storage, start, None, Order::Ascending)
         .take(limit)
         .map(|item| {
-            item.map(|(addr, ballot)| VoteInfo {
+            let (addr, ballot) = item?;
+            let rationale = RATIONALES.may_load(deps.storage, (proposal_id, &addr))?;
+            Ok(VoteInfo {
                 proposal_id,
                 voter: addr.into(),
                 vote: ballot.vote,
                 weight: ballot.weight,
+                rationale,
             })
         })
         .collect::<StdResult<_>>()?;
@@ -493,6 +2455,73 @@ fn list_votes(
     Ok(VoteListResponse { votes })
 }
 
+fn list_votes_by_voter(
+    deps: Deps,
+    voter: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<VoteListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let voter_addr = deps.api.addr_validate(&voter)?;
+    let start = start_after.map(Bound::exclusive);
+
+    let votes = VOTER_BALLOTS
+        .prefix(&voter_addr)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|proposal_id| {
+            let proposal_id = proposal_id?;
+            let ballot = BALLOTS.load(deps.storage, (proposal_id, &voter_addr))?;
+            let rationale = RATIONALES.may_load(deps.storage, (proposal_id, &voter_addr))?;
+            Ok(VoteInfo {
+                proposal_id,
+                voter: voter.clone(),
+                vote: ballot.vote,
+                weight: ballot.weight,
+                rationale,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(VoteListResponse { votes })
+}
+
+fn query_participation_stats(deps: Deps) -> StdResult<ParticipationStatsResponse> {
+    let turnouts: Vec<ProposalTurnout> = TURNOUTS
+        .range(deps.storage, None, None, Order::Descending)
+        .take(PARTICIPATION_WINDOW)
+        .map(|item| {
+            item.map(|(proposal_id, turnout)| ProposalTurnout {
+                proposal_id,
+                turnout,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    let average_turnout = if turnouts.is_empty() {
+        Decimal::zero()
+    } else {
+        let sum = turnouts
+            .iter()
+            .fold(Decimal::zero(), |sum, item| sum + item.turnout);
+        sum * Decimal::from_ratio(1u128, turnouts.len() as u128)
+    };
+    Ok(ParticipationStatsResponse {
+        turnouts,
+        average_turnout,
+    })
+}
+
+fn query_member_participation(
+    deps: Deps,
+    address: String,
+) -> StdResult<MemberParticipationResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let proposals_voted = PROPOSALS_VOTED
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    Ok(MemberParticipationResponse { proposals_voted })
+}
+
 fn query_voter(deps: Deps, voter: String) -> StdResult<VoterResponse> {
     let group_addr = GROUP.load(deps.storage)?;
     let voter_addr = deps.api.addr_validate(&voter)?;
@@ -517,3 +2546,456 @@ fn list_voters(
         .collect();
     Ok(VoterListResponse { voters })
 }
+
+fn query_delegation(deps: Deps, delegator: String) -> StdResult<DelegationResponse> {
+    let delegator = deps.api.addr_validate(&delegator)?;
+    let delegate = DELEGATIONS.may_load(deps.storage, &delegator)?;
+    Ok(DelegationResponse { delegate })
+}
+
+fn list_delegations(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DelegationListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let delegations = DELEGATIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(delegator, delegate)| Delegation {
+                delegator,
+                delegate,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(DelegationListResponse { delegations })
+}
+
+fn query_voting_power(
+    deps: Deps,
+    address: String,
+    at_height: Option<u64>,
+) -> StdResult<VotingPowerResponse> {
+    let group = GROUP.load(deps.storage)?;
+    let addr = deps.api.addr_validate(&address)?;
+
+    let weight = group
+        .is_member(&deps.querier, &addr, at_height)?
+        .unwrap_or_default();
+    let delegators = match at_height {
+        Some(height) => DELEGATORS.may_load_at_height(deps.storage, &addr, height),
+        None => DELEGATORS.may_load(deps.storage, &addr),
+    }?
+    .unwrap_or_default();
+    let delegated = delegators
+        .iter()
+        .map(|delegator| {
+            group
+                .is_member(&deps.querier, delegator, at_height)
+                .map(Option::unwrap_or_default)
+        })
+        .sum::<StdResult<u64>>()?;
+
+    Ok(VotingPowerResponse {
+        weight,
+        delegated,
+        total: weight + delegated,
+    })
+}
+
+fn query_nonce(deps: Deps, address: String) -> StdResult<NonceResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let nonce = NONCES.may_load(deps.storage, &addr)?.unwrap_or_default();
+    Ok(NonceResponse { nonce })
+}
+
+fn list_nfts(
+    deps: Deps,
+    collection: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<NftListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let collection = deps.api.addr_validate(&collection)?;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let tokens = NFTS
+        .prefix(&collection)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+
+    Ok(NftListResponse { tokens })
+}
+
+fn list_collections(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<CollectionListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let collections = COLLECTIONS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+
+    Ok(CollectionListResponse { collections })
+}
+
+fn query_treasury_balances(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TreasuryBalancesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let mut native = vec![];
+    for mut coin in deps.querier.query_all_balances(&env.contract.address)? {
+        // bids escrowed in auctions are not part of the treasury
+        let escrowed = ESCROWED_BIDS.may_load(deps.storage, &coin.denom)?;
+        coin.amount = coin.amount.saturating_sub(escrowed.unwrap_or_default());
+        if !coin.amount.is_zero() {
+            native.push(coin);
+        }
+    }
+
+    let mut cw20 = vec![];
+    let mut unavailable = vec![];
+    for token in TOKENS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+    {
+        let address = token?;
+        // any cw20 can register itself, so a failing token must not break the query
+        match treasury_balance(deps, &env.contract.address, &Denom::Cw20(address.clone())) {
+            Ok(amount) => cw20.push(Cw20Balance { address, amount }),
+            Err(_) => unavailable.push(address),
+        }
+    }
+
+    Ok(TreasuryBalancesResponse {
+        native,
+        cw20,
+        unavailable,
+    })
+}
+
+fn query_stream(deps: Deps, env: Env, id: u64) -> StdResult<StreamResponse> {
+    let stream = STREAMS.load(deps.storage, id)?;
+    Ok(StreamResponse {
+        id,
+        claimable: stream.claimable(env.block.time),
+        stream,
+    })
+}
+
+fn list_streams(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<StreamListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let streams = STREAMS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (id, stream) = item?;
+            Ok(StreamResponse {
+                id,
+                claimable: stream.claimable(env.block.time),
+                stream,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(StreamListResponse { streams })
+}
+
+fn allowance_response(mut allowance: Allowance, block: &BlockInfo) -> AllowanceResponse {
+    allowance.refresh(block);
+    AllowanceResponse {
+        remaining: allowance.cap.saturating_sub(allowance.spent),
+        denom: allowance.denom,
+        cap: allowance.cap,
+        period: allowance.period,
+        resets: allowance.resets,
+    }
+}
+
+fn query_allowance(
+    deps: Deps,
+    env: Env,
+    grantee: String,
+    denom: Denom,
+) -> StdResult<AllowanceResponse> {
+    let grantee = deps.api.addr_validate(&grantee)?;
+    let key = denom_key(&validate_denom(deps.api, denom)?);
+    let allowance = ALLOWANCES.load(deps.storage, (&grantee, &key))?;
+    Ok(allowance_response(allowance, &env.block))
+}
+
+fn list_allowances(deps: Deps, env: Env, grantee: String) -> StdResult<AllowanceListResponse> {
+    let grantee = deps.api.addr_validate(&grantee)?;
+    let allowances = ALLOWANCES
+        .prefix(&grantee)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, allowance)| allowance_response(allowance, &env.block)))
+        .collect::<StdResult<_>>()?;
+    Ok(AllowanceListResponse { allowances })
+}
+
+fn list_sub_daos(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<SubDaoListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let sub_daos = SUB_DAOS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|addr| {
+            let addr = addr?;
+            let budgets = list_allowances(deps, env.clone(), addr.to_string())?.allowances;
+            Ok(SubDaoResponse { addr, budgets })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(SubDaoListResponse { sub_daos })
+}
+
+fn query_parent(deps: Deps) -> StdResult<ParentResponse> {
+    Ok(ParentResponse {
+        parent: PARENT.may_load(deps.storage)?,
+        dissolved: DISSOLVED.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
+
+fn query_auction(deps: Deps, id: u64) -> StdResult<AuctionResponse> {
+    let auction = AUCTIONS.load(deps.storage, id)?;
+    Ok(AuctionResponse { id, auction })
+}
+
+fn list_auctions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<AuctionListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let auctions = AUCTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(id, auction)| AuctionResponse { id, auction }))
+        .collect::<StdResult<_>>()?;
+
+    Ok(AuctionListResponse { auctions })
+}
+
+fn query_revenue_split(deps: Deps, env: Env) -> StdResult<RevenueSplitResponse> {
+    let split = REVENUE_SPLIT.may_load(deps.storage)?;
+    let mut pending = vec![];
+    for denom in split.iter().flat_map(|split| &split.denoms) {
+        let (amount, _) = revenue(deps, &env, denom)?;
+        if !amount.is_zero() {
+            pending.push(coin(amount.u128(), denom));
+        }
+    }
+    Ok(RevenueSplitResponse { split, pending })
+}
+
+/// Treasury balances left after the messages simulated so far
+struct SimulatedBalances<'a> {
+    deps: Deps<'a>,
+    treasury: Addr,
+    balances: HashMap<String, Uint128>,
+}
+
+impl<'a> SimulatedBalances<'a> {
+    /// Takes `amount` out of the treasury, returning a warning if there is not enough
+    fn spend(&mut self, denom: &Denom, amount: Uint128) -> Option<String> {
+        let key = denom_key(denom);
+        let available = match self.balances.get(&key) {
+            Some(balance) => *balance,
+            None => treasury_balance(self.deps, &self.treasury, denom).unwrap_or_default(),
+        };
+        if amount > available {
+            self.balances.insert(key, Uint128::zero());
+            let name = match denom {
+                Denom::Native(denom) => denom.as_str(),
+                Denom::Cw20(address) => address.as_str(),
+            };
+            return Some(format!(
+                "insufficient {} balance: {} needed, {} available",
+                name, amount, available
+            ));
+        }
+        self.balances.insert(key, available - amount);
+        None
+    }
+
+    fn spend_coins(&mut self, coins: &[Coin]) -> Vec<String> {
+        coins
+            .iter()
+            .filter_map(|coin| self.spend(&Denom::Native(coin.denom.clone()), coin.amount))
+            .collect()
+    }
+}
+
+fn simulate_proposal(deps: Deps, env: Env, proposal_id: u64) -> StdResult<SimulationResponse> {
+    let prop = proposals().load(deps.storage, proposal_id)?;
+    let treasury = env.contract.address;
+    let msgs = proposal_msgs(&prop, &treasury)?;
+    let mut balances = SimulatedBalances {
+        deps,
+        treasury: treasury.clone(),
+        balances: HashMap::new(),
+    };
+    // NFTs transferred, sent or burned by previous messages
+    let mut moved_nfts = HashSet::new();
+
+    let mut messages = vec![];
+    for msg in &msgs {
+        let mut warnings = vec![];
+        match msg {
+            CosmosMsg::Bank(BankMsg::Send { amount, .. } | BankMsg::Burn { amount }) => {
+                warnings.extend(balances.spend_coins(amount));
+            }
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) => {
+                if deps
+                    .querier
+                    .query_wasm_contract_info(contract_addr)
+                    .is_err()
+                {
+                    warnings.push(format!("contract {} does not exist", contract_addr));
+                }
+                warnings.extend(balances.spend_coins(funds));
+
+                if let Ok(
+                    Cw721ExecuteMsg::TransferNft { token_id, .. }
+                    | Cw721ExecuteMsg::SendNft { token_id, .. }
+                    | Cw721ExecuteMsg::Burn { token_id },
+                ) = from_binary(msg)
+                {
+                    let owner: StdResult<OwnerOfResponse> = deps.querier.query_wasm_smart(
+                        contract_addr,
+                        &Cw721QueryMsg::OwnerOf {
+                            token_id: token_id.clone(),
+                            include_expired: None,
+                        },
+                    );
+                    if !matches!(owner, Ok(res) if res.owner == treasury) {
+                        warnings.push(format!(
+                            "NFT {} of {} is not held by the treasury",
+                            token_id, contract_addr
+                        ));
+                    } else if let Some(auction_id) = AUCTIONED_NFTS
+                        .may_load(deps.storage, (&Addr::unchecked(contract_addr), &token_id))?
+                    {
+                        warnings.push(format!(
+                            "NFT {} of {} is on auction {}",
+                            token_id, contract_addr, auction_id
+                        ));
+                    } else if !moved_nfts.insert((contract_addr.clone(), token_id.clone())) {
+                        warnings.push(format!(
+                            "NFT {} of {} already leaves the treasury in a previous message",
+                            token_id, contract_addr
+                        ));
+                    }
+                }
+
+                if let Ok(
+                    Cw20ExecuteMsg::Transfer { amount, .. }
+                    | Cw20ExecuteMsg::Send { amount, .. }
+                    | Cw20ExecuteMsg::Burn { amount },
+                ) = from_binary(msg)
+                {
+                    let token = Denom::Cw20(Addr::unchecked(contract_addr));
+                    warnings.extend(balances.spend(&token, amount));
+                }
+            }
+            CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. }) => {
+                warnings.extend(balances.spend_coins(funds));
+            }
+            CosmosMsg::Custom(StargazeMsgWrapper {
+                msg_data:
+                    StargazeMsg::FundCommunityPool { amount } | StargazeMsg::FundFairburnPool { amount },
+                ..
+            }) => {
+                warnings.extend(balances.spend_coins(amount));
+            }
+            CosmosMsg::Wasm(
+                WasmMsg::Migrate { contract_addr, .. }
+                | WasmMsg::UpdateAdmin { contract_addr, .. }
+                | WasmMsg::ClearAdmin { contract_addr },
+            ) if deps
+                .querier
+                .query_wasm_contract_info(contract_addr)
+                .is_err() =>
+            {
+                warnings.push(format!("contract {} does not exist", contract_addr));
+            }
+            _ => {}
+        }
+        messages.push(MessageSimulation { warnings });
+    }
+
+    Ok(SimulationResponse { messages })
+}
+
+fn query_execution_results(deps: Deps, proposal_id: u64) -> StdResult<ExecutionResultsResponse> {
+    // make sure the proposal exists
+    proposals().load(deps.storage, proposal_id)?;
+    let mode = EXECUTION_MODES
+        .may_load(deps.storage, proposal_id)?
+        .unwrap_or_default();
+
+    let results = execution_results(deps.storage, proposal_id)?;
+
+    Ok(ExecutionResultsResponse {
+        mode,
+        status: execution_status(&results),
+        results,
+    })
+}
+
+fn execution_results(storage: &dyn Storage, proposal_id: u64) -> StdResult<Vec<MessageResult>> {
+    EXECUTION_RESULTS
+        .prefix(proposal_id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, result)| result))
+        .collect()
+}
+
+/// None until a best effort execution has recorded results
+fn execution_status(results: &[MessageResult]) -> Option<ExecutionStatus> {
+    let failed = |result: &MessageResult| matches!(result, MessageResult::Failure { .. });
+    if results.is_empty() {
+        None
+    } else if results.iter().any(failed) {
+        Some(ExecutionStatus::ExecutionFailed)
+    } else {
+        Some(ExecutionStatus::Succeeded)
+    }
+}
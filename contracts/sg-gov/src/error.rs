@@ -1,6 +1,6 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use cw3_flex_multisig::ContractError as Cw3FlexMultisigError;
-use cw_utils::ThresholdError;
+use cw_utils::{Expiration, PaymentError, ThresholdError};
 use thiserror::Error;
 
 #[derive(Error, PartialEq, Debug)]
@@ -14,6 +14,9 @@ pub enum ContractError {
     #[error("{0}")]
     Threshold(#[from] ThresholdError),
 
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
     #[error("Invalid reply ID")]
     InvalidReplyID {},
 
@@ -38,6 +41,15 @@ pub enum ContractError {
     #[error("Wrong expiration option")]
     WrongExpiration {},
 
+    #[error("Proposal {field} must not be empty or longer than {max} characters")]
+    InvalidMetadata { field: String, max: usize },
+
+    #[error("Invalid proposal action")]
+    InvalidAction {},
+
+    #[error("Minimum voting period must use the same unit as the maximum and not exceed it")]
+    InvalidMinVotingPeriod {},
+
     #[error("Already voted on this proposal")]
     AlreadyVoted {},
 
@@ -46,4 +58,82 @@ pub enum ContractError {
 
     #[error("Cannot close completed or passed proposals")]
     WrongCloseStatus {},
+
+    #[error("Vote rationale is longer than {max} characters")]
+    RationaleTooLong { max: usize },
+
+    #[error("Signed vote is for another contract")]
+    WrongContract {},
+
+    #[error("Invalid signature")]
+    InvalidSignature {},
+
+    #[error("Public key must be a compressed secp256k1 key")]
+    InvalidPubkey {},
+
+    #[error("Invalid nonce, expected {expected}")]
+    InvalidNonce { expected: u64 },
+
+    #[error("Cannot delegate to yourself")]
+    SelfDelegation {},
+
+    #[error("No delegation found")]
+    NoDelegation {},
+
+    #[error("Not a cw20 token: {addr}")]
+    InvalidToken { addr: String },
+
+    #[error("Invalid stream: end must be after start, interval and amount must be non-zero and cliff must be within the stream")]
+    InvalidStream {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("Allowance cap and period must be non-zero")]
+    InvalidAllowance {},
+
+    #[error("Grace period must use the same unit as the max voting period")]
+    InvalidGracePeriod {},
+
+    #[error("Proposal cannot be executed before the grace period ends at {ends}")]
+    GracePeriod { ends: Expiration },
+
+    #[error("Cannot ragequit while voting yes on pending proposal {proposal_id}")]
+    PendingYesVote { proposal_id: u64 },
+
+    #[error("Allowance exceeded, {remaining} left in this period")]
+    AllowanceExceeded { remaining: Uint128 },
+
+    #[error("The treasury does not hold NFT {token_id} of {collection}")]
+    NftNotHeld {
+        collection: String,
+        token_id: String,
+    },
+
+    #[error("Treasury only holds {available} outside of escrowed bids")]
+    InsufficientTreasury { available: Uint128 },
+
+    #[error("Auction has ended")]
+    AuctionEnded {},
+
+    #[error("Auction has not ended yet")]
+    AuctionNotEnded {},
+
+    #[error("Bid must be at least {min}")]
+    BidTooLow { min: Uint128 },
+
+    #[error("Group share must not be more than 1")]
+    InvalidRevenueSplit {},
+
+    #[error("No revenue split configured")]
+    NoRevenueSplit {},
+
+    #[error("Nothing to distribute")]
+    NothingToDistribute {},
+
+    #[error("DAO was dissolved by its parent")]
+    Dissolved {},
+
+    #[error("{addr} is not a sub-DAO")]
+    NotSubDao { addr: String },
 }
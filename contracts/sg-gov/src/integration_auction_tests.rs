@@ -0,0 +1,335 @@
+#[cfg(test)]
+mod tests {
+    use std::vec;
+
+    use crate::integration_helpers::*;
+    use crate::{
+        msg::{
+            AuctionListResponse, ExecuteMsg, NftListResponse, QueryMsg, SimulationResponse,
+            TreasuryBalancesResponse,
+        },
+        ContractError,
+    };
+    use cosmwasm_std::{coin, coins, to_binary, Addr, Event, Uint128, WasmMsg};
+    use cw20::Denom;
+    use cw721::{Cw721QueryMsg, OwnerOfResponse};
+    use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension};
+    use cw_multi_test::Executor;
+    use cw_utils::{Duration, Threshold};
+
+    #[test]
+    fn treasury_auctions_nft() {
+        let mut app = mock_app(&coins(2000, "ustars"));
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, vec![], None);
+        for bidder in [VOTER1, VOTER2] {
+            app.send_tokens(
+                Addr::unchecked(OWNER),
+                Addr::unchecked(bidder),
+                &coins(1000, "ustars"),
+            )
+            .unwrap();
+        }
+
+        let collection_addr = setup_test_collection(&mut app);
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            collection_addr.clone(),
+            &Cw721ExecuteMsg::SendNft::<Extension, Extension> {
+                contract: dao_addr.to_string(),
+                token_id: TOKEN_ID.to_string(),
+                msg: to_binary("unused").unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(
+                &dao_addr,
+                &ExecuteMsg::StartAuction {
+                    collection: collection_addr.to_string(),
+                    token_id: TOKEN_ID.to_string(),
+                    denom: "ustars".to_string(),
+                    reserve_price: Uint128::new(100),
+                    duration: Duration::Time(1000),
+                },
+            ),
+        );
+
+        let bid = |app: &mut App, bidder: &str, amount: u128| {
+            app.execute_contract(
+                Addr::unchecked(bidder),
+                dao_addr.clone(),
+                &ExecuteMsg::Bid { auction_id: 1 },
+                &coins(amount, "ustars"),
+            )
+        };
+
+        let err = bid(&mut app, VOTER1, 50).unwrap_err();
+        assert_eq!(
+            ContractError::BidTooLow {
+                min: Uint128::new(100)
+            },
+            err.downcast().unwrap()
+        );
+        bid(&mut app, VOTER1, 100).unwrap();
+        let err = bid(&mut app, VOTER2, 100).unwrap_err();
+        assert_eq!(
+            ContractError::BidTooLow {
+                min: Uint128::new(101)
+            },
+            err.downcast().unwrap()
+        );
+
+        // the outbid bidder is refunded
+        bid(&mut app, VOTER2, 150).unwrap();
+        assert_eq!(
+            app.wrap().query_balance(VOTER1, "ustars").unwrap(),
+            coin(1000, "ustars")
+        );
+
+        // escrowed bids are not part of the treasury
+        let balances: TreasuryBalancesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::TreasuryBalances {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert!(balances.native.is_empty());
+
+        // and cannot be paid out of the treasury
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(
+                &dao_addr,
+                &ExecuteMsg::SetAllowance {
+                    grantee: VOTER3.to_string(),
+                    denom: Denom::Native("ustars".to_string()),
+                    cap: Uint128::new(100),
+                    period: Duration::Time(1000),
+                },
+            ),
+        );
+        let err = app
+            .execute_contract(
+                Addr::unchecked(VOTER3),
+                dao_addr.clone(),
+                &ExecuteMsg::Spend {
+                    to: SOMEBODY.to_string(),
+                    denom: Denom::Native("ustars".to_string()),
+                    amount: Uint128::new(100),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InsufficientTreasury {
+                available: Uint128::zero()
+            },
+            err.downcast().unwrap()
+        );
+
+        let settle = ExecuteMsg::SettleAuction { auction_id: 1 };
+        let err = app
+            .execute_contract(Addr::unchecked(SOMEBODY), dao_addr.clone(), &settle, &[])
+            .unwrap_err();
+        assert_eq!(ContractError::AuctionNotEnded {}, err.downcast().unwrap());
+
+        app.update_block(expire(Duration::Time(1000)));
+        let err = bid(&mut app, VOTER1, 200).unwrap_err();
+        assert_eq!(ContractError::AuctionEnded {}, err.downcast().unwrap());
+
+        let res = app
+            .execute_contract(Addr::unchecked(SOMEBODY), dao_addr.clone(), &settle, &[])
+            .unwrap();
+        assert_eq!(
+            res.custom_attrs(1),
+            [
+                ("action", "settle_auction"),
+                ("sender", SOMEBODY),
+                ("auction_id", "1"),
+                ("winner", VOTER2),
+                ("amount", "150"),
+            ],
+        );
+
+        // the NFT went to the winner and the proceeds to the treasury
+        let res: OwnerOfResponse = app
+            .wrap()
+            .query_wasm_smart(
+                collection_addr,
+                &Cw721QueryMsg::OwnerOf {
+                    token_id: TOKEN_ID.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(res.owner, VOTER2);
+        let balances: TreasuryBalancesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::TreasuryBalances {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(balances.native, coins(150, "ustars"));
+        let auctions: AuctionListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::ListAuctions {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert!(auctions.auctions.is_empty());
+    }
+
+    #[test]
+    fn auction_bids_refunded_when_cancelled_or_nft_moved() {
+        let mut app = mock_app(&coins(1000, "ustars"));
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, vec![], None);
+        app.send_tokens(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(VOTER1),
+            &coins(1000, "ustars"),
+        )
+        .unwrap();
+
+        let collection_addr = setup_test_collection(&mut app);
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            collection_addr.clone(),
+            &Cw721ExecuteMsg::SendNft::<Extension, Extension> {
+                contract: dao_addr.to_string(),
+                token_id: TOKEN_ID.to_string(),
+                msg: to_binary("unused").unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let start_auction = ExecuteMsg::StartAuction {
+            collection: collection_addr.to_string(),
+            token_id: TOKEN_ID.to_string(),
+            denom: "ustars".to_string(),
+            reserve_price: Uint128::new(100),
+            duration: Duration::Time(1000),
+        };
+        let bid = |app: &mut App, auction_id: u64| {
+            app.execute_contract(
+                Addr::unchecked(VOTER1),
+                dao_addr.clone(),
+                &ExecuteMsg::Bid { auction_id },
+                &coins(100, "ustars"),
+            )
+            .unwrap();
+        };
+        let voter1_balance = |app: &App| app.wrap().query_balance(VOTER1, "ustars").unwrap().amount;
+
+        // cancelling an auction refunds the highest bid and takes the NFT back
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(&dao_addr, &start_auction),
+        );
+        bid(&mut app, 1);
+        assert_eq!(voter1_balance(&app), Uint128::new(900));
+        let err = app
+            .execute_contract(
+                Addr::unchecked(VOTER1),
+                dao_addr.clone(),
+                &ExecuteMsg::CancelAuction { auction_id: 1 },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(&dao_addr, &ExecuteMsg::CancelAuction { auction_id: 1 }),
+        );
+        assert_eq!(voter1_balance(&app), Uint128::new(1000));
+        let nfts: NftListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::ListNfts {
+                    collection: collection_addr.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(nfts.tokens, vec![TOKEN_ID.to_string()]);
+
+        // moving an NFT on auction is flagged when simulating the proposal
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(&dao_addr, &start_auction),
+        );
+        bid(&mut app, 2);
+        let msgs = vec![WasmMsg::Execute {
+            contract_addr: collection_addr.to_string(),
+            msg: to_binary(&Cw721ExecuteMsg::TransferNft::<Extension, Extension> {
+                recipient: SOMEBODY.to_string(),
+                token_id: TOKEN_ID.to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()];
+        let proposal_id = propose(&mut app, &dao_addr, OWNER, msgs);
+        let res: SimulationResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::SimulateProposal { proposal_id })
+            .unwrap();
+        assert_eq!(
+            res.messages[0].warnings,
+            vec![format!(
+                "NFT {} of {} is on auction 2",
+                TOKEN_ID, collection_addr
+            )]
+        );
+        execute_proposal(&mut app, &dao_addr, proposal_id);
+
+        // the NFT can no longer be transferred to the winner, who gets their bid back
+        app.update_block(expire(Duration::Time(1000)));
+        let res = app
+            .execute_contract(
+                Addr::unchecked(SOMEBODY),
+                dao_addr.clone(),
+                &ExecuteMsg::SettleAuction { auction_id: 2 },
+                &[],
+            )
+            .unwrap();
+        assert!(res.has_event(
+            &Event::new("wasm")
+                .add_attribute("action", "settle_auction_refund")
+                .add_attribute("bidder", VOTER1)
+        ));
+        assert_eq!(voter1_balance(&app), Uint128::new(1000));
+        assert_eq!(
+            app.wrap().query_balance(&dao_addr, "ustars").unwrap(),
+            coin(0, "ustars")
+        );
+    }
+}
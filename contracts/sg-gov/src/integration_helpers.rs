@@ -0,0 +1,644 @@
+//! Fixtures shared by the integration tests: a DAO governed by an sg-nft-group
+//! whose members hold the NFTs of a collection, and shortcuts to run its proposals.
+
+use bech32::{ToBase32, Variant};
+use cosmwasm_std::{
+    coins, from_binary,
+    testing::{MockApi, MockStorage},
+    to_binary, Addr, Api, BankMsg, Binary, BlockInfo, CanonicalAddr, Coin, Deps, DepsMut, Empty,
+    Env, MessageInfo, RecoverPubkeyError, Response, StdError, StdResult, Uint128,
+    VerificationError, WasmMsg,
+};
+use cw20::{Cw20Coin, Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use cw3::{Status, Vote};
+use cw4::Member;
+use cw721_base::{
+    msg::{ExecuteMsg as Cw721ExecuteMsg, InstantiateMsg as Cw721InstantiateMsg},
+    Extension, MintMsg,
+};
+use cw_multi_test::{
+    next_block, AppResponse, BankKeeper, BasicAppBuilder, Contract, ContractWrapper, Executor,
+    WasmKeeper,
+};
+use cw_utils::{Duration, Threshold};
+use sg_daos::{Admin, ContractInstantiateMsg};
+use sg_multi_test::StargazeModule;
+use sg_std::{CosmosMsg, StargazeMsgWrapper};
+
+use crate::msg::{ExecuteMsg, Group, InstantiateMsg, ProposalResponse, QueryMsg};
+use crate::ContractError;
+
+pub(crate) const OWNER: &str = "admin0001";
+pub(crate) const VOTER1: &str = "voter0001";
+pub(crate) const VOTER2: &str = "voter0002";
+pub(crate) const VOTER3: &str = "voter0003";
+pub(crate) const VOTER4: &str = "voter0004";
+pub(crate) const VOTER5: &str = "voter0005";
+pub(crate) const SOMEBODY: &str = "somebody";
+
+pub(crate) const COLLECTION_CONTRACT: &str = "contract0";
+pub(crate) const SG_NFT_GROUP_CONTRACT: &str = "contract1";
+
+pub(crate) const TOKEN_ID: &str = "token0001";
+pub(crate) const MINTER: &str = "minter";
+
+pub(crate) fn member<T: Into<String>>(addr: T, weight: u64) -> Member {
+    Member {
+        addr: addr.into(),
+        weight,
+    }
+}
+
+pub(crate) fn members() -> Vec<Member> {
+    vec![
+        member(OWNER, 1),
+        member(VOTER1, 1),
+        member(VOTER2, 2),
+        member(VOTER3, 3),
+        member(VOTER4, 12), // so that he alone can pass a 50 / 52% threshold proposal
+        member(VOTER5, 5),
+    ]
+}
+
+pub(crate) fn contract_nft_dao() -> Box<dyn Contract<StargazeMsgWrapper>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_reply(crate::contract::reply);
+    Box::new(contract)
+}
+
+pub(crate) fn contract_nft_group() -> Box<dyn Contract<StargazeMsgWrapper>> {
+    let contract = ContractWrapper::new_with_empty(
+        sg_nft_group::contract::execute,
+        sg_nft_group::contract::instantiate,
+        sg_nft_group::contract::query,
+    )
+    .with_reply_empty(sg_nft_group::contract::reply);
+    Box::new(contract)
+}
+
+pub(crate) fn contract_cw721() -> Box<dyn Contract<StargazeMsgWrapper>> {
+    let contract = ContractWrapper::new_with_empty(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    );
+    Box::new(contract)
+}
+
+pub(crate) fn contract_cw20() -> Box<dyn Contract<StargazeMsgWrapper>> {
+    let contract = ContractWrapper::new_with_empty(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+pub(crate) const BECH32_PREFIX: &str = "stars";
+
+/// Mock API that renders 20 byte canonical addresses as bech32,
+/// like the chain does for addresses derived from public keys
+#[derive(Default)]
+pub(crate) struct TestApi(MockApi);
+
+impl Api for TestApi {
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        self.0.addr_validate(human)
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        self.0.addr_canonicalize(human)
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        if canonical.len() == 20 {
+            let addr = bech32::encode(
+                BECH32_PREFIX,
+                canonical.as_slice().to_base32(),
+                Variant::Bech32,
+            )
+            .unwrap();
+            return Ok(Addr::unchecked(addr));
+        }
+        self.0.addr_humanize(canonical)
+    }
+
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.secp256k1_verify(message_hash, signature, public_key)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8,
+    ) -> Result<Vec<u8>, RecoverPubkeyError> {
+        self.0
+            .secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.ed25519_verify(message, signature, public_key)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        self.0
+            .ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn debug(&self, message: &str) {
+        self.0.debug(message)
+    }
+}
+
+pub(crate) type App = cw_multi_test::App<
+    BankKeeper,
+    TestApi,
+    MockStorage,
+    StargazeModule,
+    WasmKeeper<StargazeMsgWrapper, Empty>,
+>;
+
+pub(crate) fn mock_app(init_funds: &[Coin]) -> App {
+    BasicAppBuilder::<StargazeMsgWrapper, Empty>::new_custom()
+        .with_api(TestApi::default())
+        .with_custom(StargazeModule {})
+        .build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked(OWNER), init_funds.to_vec())
+                .unwrap();
+        })
+}
+
+/// create a sg_nft_group initialized with the given members
+pub(crate) fn sg_nft_group_init_info(app: &mut App) -> ContractInstantiateMsg {
+    let group_id = app.store_code(contract_nft_group());
+    let collection_code_id = app.store_code(contract_cw721());
+
+    let msg = Cw721InstantiateMsg {
+        name: "MemberCollection".to_string(),
+        symbol: "SGMC".to_string(),
+        minter: SG_NFT_GROUP_CONTRACT.to_string(),
+    };
+
+    let cw721_init_msg = ContractInstantiateMsg {
+        code_id: collection_code_id,
+        msg: to_binary(&msg).unwrap(),
+        admin: Some(Admin::Creator {}),
+        label: "MemberCollection".to_string(),
+    };
+
+    let collection = instantiate_collection(app);
+    let msg = sg_nft_group::msg::InstantiateMsg {
+        collection: collection.to_string(),
+        cw721_init_msg,
+    };
+
+    ContractInstantiateMsg {
+        code_id: group_id,
+        msg: to_binary(&msg).unwrap(),
+        admin: Some(Admin::Creator {}),
+        label: "Test-Group".to_string(),
+    }
+}
+
+pub(crate) fn mint_into_collection(app: &mut App, owner: String, token_id: String) {
+    let mint_msg = Cw721ExecuteMsg::Mint::<Extension, Extension>(MintMsg::<Extension> {
+        token_id,
+        owner,
+        token_uri: None,
+        extension: None,
+    });
+
+    app.execute_contract(
+        Addr::unchecked(MINTER),
+        Addr::unchecked(COLLECTION_CONTRACT),
+        &mint_msg,
+        &[],
+    )
+    .unwrap();
+}
+
+pub(crate) fn join_group(app: &mut App, sender: String, token_id: String) {
+    let msg = to_binary("This is unused").unwrap();
+
+    let send_nft_msg = Cw721ExecuteMsg::SendNft::<Extension, Extension> {
+        contract: SG_NFT_GROUP_CONTRACT.to_string(),
+        token_id,
+        msg,
+    };
+    app.execute_contract(
+        Addr::unchecked(sender),
+        Addr::unchecked(COLLECTION_CONTRACT),
+        &send_nft_msg,
+        &[],
+    )
+    .unwrap();
+}
+
+pub(crate) fn mint_and_join_nft_group(app: &mut App, members: Vec<Member>) {
+    for member in members {
+        for i in 0..member.weight {
+            let token_id = format!("{}/{}", member.clone().addr, i);
+            mint_into_collection(app, member.clone().addr, token_id.clone());
+            join_group(app, member.clone().addr, token_id);
+        }
+    }
+}
+
+#[track_caller]
+pub(crate) fn instantiate_dao(
+    app: &mut App,
+    threshold: Threshold,
+    max_voting_period: Duration,
+    min_voting_period: Option<Duration>,
+    grace_period: Option<Duration>,
+    executor: Option<crate::state::Executor>,
+) -> Addr {
+    let dao_id = app.store_code(contract_nft_dao());
+    let init_group = sg_nft_group_init_info(app);
+    let init_msg: sg_nft_group::msg::InstantiateMsg = from_binary(&init_group.msg).unwrap();
+    let group_addr = app
+        .instantiate_contract(
+            init_group.code_id,
+            Addr::unchecked(OWNER),
+            &init_msg,
+            &[],
+            init_group.label,
+            Some(OWNER.to_string()),
+        )
+        .unwrap();
+    let msg = InstantiateMsg {
+        name: "name".to_string(),
+        description: "description".to_string(),
+        image: "image".to_string(),
+        group: Group::Cw4Address(group_addr.to_string()),
+        threshold,
+        max_voting_period,
+        min_voting_period,
+        grace_period,
+        executor,
+        parent: None,
+        registry: None,
+    };
+    mint_and_join_nft_group(app, members());
+    let dao_addr = app
+        .instantiate_contract(dao_id, Addr::unchecked(OWNER), &msg, &[], "dao", None)
+        .unwrap();
+
+    // the group pays ragequits out of the treasury of the DAO administering it
+    app.execute(
+        Addr::unchecked(OWNER),
+        WasmMsg::UpdateAdmin {
+            contract_addr: group_addr.to_string(),
+            admin: dao_addr.to_string(),
+        }
+        .into(),
+    )
+    .unwrap();
+    dao_addr
+}
+
+// this will set up both contracts, instantiating the group with
+// all voters defined above, and the multisig pointing to it and given threshold criteria.
+// Returns multisig address.
+#[track_caller]
+pub(crate) fn setup_test_case_fixed(
+    app: &mut App,
+    weight_needed: u64,
+    max_voting_period: Duration,
+    init_funds: Vec<Coin>,
+) -> Addr {
+    setup_test_case(
+        app,
+        Threshold::AbsoluteCount {
+            weight: weight_needed,
+        },
+        max_voting_period,
+        init_funds,
+        None,
+    )
+}
+
+#[track_caller]
+pub(crate) fn setup_test_case(
+    app: &mut App,
+    threshold: Threshold,
+    max_voting_period: Duration,
+    init_funds: Vec<Coin>,
+    executor: Option<crate::state::Executor>,
+) -> Addr {
+    let dao_addr = instantiate_dao(app, threshold, max_voting_period, None, None, executor);
+    app.update_block(next_block);
+
+    // Bonus: set some funds on the multisig contract for future proposals
+    if !init_funds.is_empty() {
+        app.send_tokens(Addr::unchecked(OWNER), dao_addr.clone(), &init_funds)
+            .unwrap();
+    }
+    dao_addr
+}
+
+pub(crate) fn proposal_info() -> (Vec<CosmosMsg>, String, String) {
+    let bank_msg = BankMsg::Send {
+        to_address: SOMEBODY.into(),
+        amount: coins(1, "BTC"),
+    };
+    let msgs = vec![bank_msg.into()];
+    let title = "Pay somebody".to_string();
+    let description = "Do I pay her?".to_string();
+    (msgs, title, description)
+}
+
+pub(crate) fn pay_somebody_proposal() -> ExecuteMsg {
+    let (msgs, title, description) = proposal_info();
+    ExecuteMsg::Propose {
+        title,
+        description,
+        msgs,
+        actions: vec![],
+        metadata: None,
+        latest: None,
+        execution_mode: None,
+        proposer_vote: None,
+    }
+}
+
+pub(crate) fn expire(voting_period: Duration) -> impl Fn(&mut BlockInfo) {
+    move |block: &mut BlockInfo| {
+        match voting_period {
+            Duration::Time(duration) => block.time = block.time.plus_seconds(duration + 1),
+            Duration::Height(duration) => block.height += duration + 1,
+        };
+    }
+}
+
+#[track_caller]
+pub(crate) fn instantiate_collection(app: &mut App) -> Addr {
+    let collection_code_id = app.store_code(contract_cw721());
+    let msg = Cw721InstantiateMsg {
+        name: "My NFTs".to_string(),
+        symbol: "NFT".to_string(),
+        minter: MINTER.into(),
+    };
+    app.instantiate_contract(
+        collection_code_id,
+        Addr::unchecked(OWNER),
+        &msg,
+        &[],
+        "collection",
+        None,
+    )
+    .unwrap()
+}
+
+#[track_caller]
+pub(crate) fn setup_test_collection(app: &mut App) -> Addr {
+    let collection_addr = instantiate_collection(app);
+    app.update_block(next_block);
+
+    // mint an NFT
+    let token_id = TOKEN_ID.to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+
+    let mint_msg = Cw721ExecuteMsg::Mint::<Extension, Extension>(MintMsg::<Extension> {
+        token_id,
+        owner: OWNER.into(),
+        token_uri: Some(token_uri),
+        extension: None,
+    });
+
+    app.execute_contract(
+        Addr::unchecked(MINTER),
+        collection_addr.clone(),
+        &mint_msg,
+        &[],
+    )
+    .unwrap();
+
+    collection_addr
+}
+
+/// Creates a proposal of `msgs` as `proposer`, with the default initial vote,
+/// and returns its id
+#[track_caller]
+pub(crate) fn propose(app: &mut App, dao: &Addr, proposer: &str, msgs: Vec<CosmosMsg>) -> u64 {
+    let res = app
+        .execute_contract(
+            Addr::unchecked(proposer),
+            dao.clone(),
+            &ExecuteMsg::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs,
+                actions: vec![],
+                metadata: None,
+                latest: None,
+                execution_mode: None,
+                proposer_vote: None,
+            },
+            &[],
+        )
+        .unwrap();
+    proposal_id(&res)
+}
+
+/// Id of the proposal created by a `Propose`
+pub(crate) fn proposal_id(res: &AppResponse) -> u64 {
+    res.custom_attrs(1)[2].value.parse().unwrap()
+}
+
+#[track_caller]
+pub(crate) fn vote(
+    app: &mut App,
+    dao: &Addr,
+    voter: &str,
+    proposal_id: u64,
+    vote: Vote,
+) -> AppResponse {
+    app.execute_contract(
+        Addr::unchecked(voter),
+        dao.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap()
+}
+
+/// Status of the proposal as reported by the `Proposal` query
+pub(crate) fn status(app: &App, dao: &Addr, proposal_id: u64) -> Status {
+    let prop: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(dao, &QueryMsg::Proposal { proposal_id })
+        .unwrap();
+    prop.status
+}
+
+#[track_caller]
+pub(crate) fn execute_proposal(app: &mut App, dao: &Addr, proposal_id: u64) -> AppResponse {
+    app.execute_contract(
+        Addr::unchecked(SOMEBODY),
+        dao.clone(),
+        &ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .unwrap()
+}
+
+/// `msg` sent by the DAO to itself, for the messages only proposals can send
+pub(crate) fn dao_msg(dao: &Addr, msg: &ExecuteMsg) -> WasmMsg {
+    WasmMsg::Execute {
+        contract_addr: dao.to_string(),
+        msg: to_binary(msg).unwrap(),
+        funds: vec![],
+    }
+}
+
+/// Proposes `msg` as OWNER, passes it with the vote of VOTER4 and executes it
+#[track_caller]
+pub(crate) fn propose_pass_execute(app: &mut App, dao_addr: Addr, msg: WasmMsg) {
+    let proposal_id = propose(app, &dao_addr, OWNER, vec![CosmosMsg::Wasm(msg)]);
+
+    let res = vote(app, &dao_addr, VOTER4, proposal_id, Vote::Yes);
+    assert_eq!(
+        res.custom_attrs(1),
+        [
+            ("action", "vote"),
+            ("sender", VOTER4),
+            ("proposal_id", proposal_id.to_string().as_str()),
+            ("status", "Passed"),
+        ],
+    );
+
+    // In passing: Try to close Passed fails
+    let closing = ExecuteMsg::Close { proposal_id };
+    let err = app
+        .execute_contract(Addr::unchecked(OWNER), dao_addr.clone(), &closing, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::WrongCloseStatus {}, err.downcast().unwrap());
+
+    let res = execute_proposal(app, &dao_addr, proposal_id);
+    assert_eq!(
+        res.custom_attrs(1),
+        [
+            ("action", "execute"),
+            ("sender", SOMEBODY),
+            ("proposal_id", proposal_id.to_string().as_str()),
+        ],
+    );
+
+    // Trying to execute something that was already executed fails
+    let execution = ExecuteMsg::Execute { proposal_id };
+    let err = app
+        .execute_contract(Addr::unchecked(SOMEBODY), dao_addr, &execution, &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::WrongExecuteStatus {},
+        err.downcast().unwrap()
+    );
+}
+
+/// A token that registers itself with `Send`, but fails every query
+pub(crate) fn instantiate_broken_cw20(app: &mut App) -> Addr {
+    fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+    fn execute(_: DepsMut, _: Env, info: MessageInfo, msg: Cw20ExecuteMsg) -> StdResult<Response> {
+        match msg {
+            Cw20ExecuteMsg::Send {
+                contract,
+                amount,
+                msg,
+            } => Ok(Response::new().add_message(WasmMsg::Execute {
+                contract_addr: contract,
+                msg: to_binary(&ExecuteMsg::Receive(Cw20ReceiveMsg {
+                    sender: info.sender.to_string(),
+                    amount,
+                    msg,
+                }))?,
+                funds: vec![],
+            })),
+            _ => Err(StdError::generic_err("unsupported")),
+        }
+    }
+    fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+        Err(StdError::generic_err("broken"))
+    }
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        execute,
+        instantiate,
+        query,
+    )));
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(OWNER),
+        &Empty {},
+        &[],
+        "broken",
+        None,
+    )
+    .unwrap()
+}
+
+pub(crate) fn send_broken_cw20(app: &mut App, token: &Addr, to: &Addr) {
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        token.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: to.to_string(),
+            amount: Uint128::new(1),
+            msg: to_binary("unused").unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+pub(crate) fn instantiate_cw20(app: &mut App, symbol: &str, amount: u128) -> Addr {
+    let code_id = app.store_code(contract_cw20());
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(OWNER),
+        &Cw20InstantiateMsg {
+            name: format!("{symbol} token"),
+            symbol: symbol.to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: OWNER.to_string(),
+                amount: Uint128::new(amount),
+            }],
+            mint: None,
+            marketing: None,
+        },
+        &[],
+        symbol,
+        None,
+    )
+    .unwrap()
+}
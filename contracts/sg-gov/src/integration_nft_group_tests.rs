@@ -2,267 +2,46 @@
 mod tests {
     use std::vec;
 
+    use crate::integration_helpers::*;
     use crate::{
         contract::{CONTRACT_NAME, CONTRACT_VERSION},
-        msg::{ExecuteMsg, Group, InstantiateMsg, MetadataResponse, QueryMsg},
+        msg::{
+            Delegation, DelegationListResponse, DelegationResponse, ExecuteMsg,
+            ExecutionResultsResponse, ExecutionStatus, Group, InstantiateMsg,
+            MemberParticipationResponse, MetadataResponse, NftListResponse, NonceResponse,
+            ParticipationStatsResponse, ProposalListResponse, ProposalResponse, ProposalTurnout,
+            ProposerVote, QueryMsg, SignedVote, SignedVotePayload, SimulationResponse, VoteInfo,
+            VoteListResponse, VoteResponse, VotingPowerResponse,
+        },
+        state::{
+            ExecutionMode, MessageResult, ProposalAction, ProposalCategory, ProposalMetadata,
+            MAX_DISCUSSION_URL_LENGTH, MAX_RATIONALE_LENGTH,
+        },
         ContractError,
     };
+    use bech32::{ToBase32, Variant};
     use cosmwasm_std::{
-        coin, coins, from_binary, to_binary, Addr, BankMsg, BlockInfo, Coin, CosmosMsg, Decimal,
-        Empty, Timestamp, WasmMsg,
+        coin, coins, from_binary, to_binary, to_vec, Addr, BankMsg, BlockInfo, Coin, Decimal,
+        Timestamp, Uint128, WasmMsg,
     };
     use cw2::{query_contract_info, ContractVersion};
-    use cw3::{
-        ProposalListResponse, ProposalResponse, Status, Vote, VoteInfo, VoteListResponse,
-        VoteResponse, VoterDetail, VoterListResponse,
-    };
-    use cw4::Member;
+    use cw20::{Cw20ExecuteMsg, Denom};
+    use cw3::{Status, Vote, VoterDetail, VoterListResponse, VoterResponse};
     use cw721::{ContractInfoResponse, Cw721QueryMsg, OwnerOfResponse};
     use cw721_base::{
         msg::{ExecuteMsg as Cw721ExecuteMsg, InstantiateMsg as Cw721InstantiateMsg},
         Extension, MintMsg,
     };
-    use cw_multi_test::{next_block, App, AppBuilder, Contract, ContractWrapper, Executor};
+    use cw_multi_test::{next_block, Executor};
     use cw_utils::{Duration, Expiration, Threshold, ThresholdResponse};
-    use sg_daos::{Admin, ContractInstantiateMsg};
-
-    const OWNER: &str = "admin0001";
-    const VOTER1: &str = "voter0001";
-    const VOTER2: &str = "voter0002";
-    const VOTER3: &str = "voter0003";
-    const VOTER4: &str = "voter0004";
-    const VOTER5: &str = "voter0005";
-    const SOMEBODY: &str = "somebody";
-
-    const COLLECTION_CONTRACT: &str = "contract0";
-    const SG_NFT_GROUP_CONTRACT: &str = "contract1";
-
-    fn member<T: Into<String>>(addr: T, weight: u64) -> Member {
-        Member {
-            addr: addr.into(),
-            weight,
-        }
-    }
-
-    fn members() -> Vec<Member> {
-        vec![
-            member(OWNER, 1),
-            member(VOTER1, 1),
-            member(VOTER2, 2),
-            member(VOTER3, 3),
-            member(VOTER4, 12), // so that he alone can pass a 50 / 52% threshold proposal
-            member(VOTER5, 5),
-        ]
-    }
-
-    pub fn contract_nft_dao() -> Box<dyn Contract<Empty>> {
-        let contract = ContractWrapper::new(
-            crate::contract::execute,
-            crate::contract::instantiate,
-            crate::contract::query,
-        )
-        .with_reply(crate::contract::reply);
-        Box::new(contract)
-    }
-
-    pub fn contract_nft_group() -> Box<dyn Contract<Empty>> {
-        let contract = ContractWrapper::new(
-            sg_nft_group::contract::execute,
-            sg_nft_group::contract::instantiate,
-            sg_nft_group::contract::query,
-        )
-        .with_reply(sg_nft_group::contract::reply);
-        Box::new(contract)
-    }
-
-    pub fn contract_cw721() -> Box<dyn Contract<Empty>> {
-        let contract = ContractWrapper::new(
-            cw721_base::entry::execute,
-            cw721_base::entry::instantiate,
-            cw721_base::entry::query,
-        );
-        Box::new(contract)
-    }
-
-    fn mock_app(init_funds: &[Coin]) -> App {
-        AppBuilder::new().build(|router, _, storage| {
-            router
-                .bank
-                .init_balance(storage, &Addr::unchecked(OWNER), init_funds.to_vec())
-                .unwrap();
-        })
-    }
-
-    /// create a sg_nft_group initialized with the given members
-    fn sg_nft_group_init_info(app: &mut App) -> ContractInstantiateMsg {
-        let group_id = app.store_code(contract_nft_group());
-        let collection_code_id = app.store_code(contract_cw721());
-
-        let msg = Cw721InstantiateMsg {
-            name: "MemberCollection".to_string(),
-            symbol: "SGMC".to_string(),
-            minter: SG_NFT_GROUP_CONTRACT.to_string(),
-        };
-
-        let cw721_init_msg = ContractInstantiateMsg {
-            code_id: collection_code_id,
-            msg: to_binary(&msg).unwrap(),
-            admin: Some(Admin::Creator {}),
-            label: "MemberCollection".to_string(),
-        };
-
-        let collection = instantiate_collection(app);
-        let msg = sg_nft_group::msg::InstantiateMsg {
-            collection: collection.to_string(),
-            cw721_init_msg,
-        };
-
-        ContractInstantiateMsg {
-            code_id: group_id,
-            msg: to_binary(&msg).unwrap(),
-            admin: Some(Admin::Creator {}),
-            label: "Test-Group".to_string(),
-        }
-    }
-
-    fn mint_into_collection(app: &mut App, owner: String, token_id: String) {
-        let mint_msg = Cw721ExecuteMsg::Mint::<Extension, Extension>(MintMsg::<Extension> {
-            token_id,
-            owner,
-            token_uri: None,
-            extension: None,
-        });
-
-        app.execute_contract(
-            Addr::unchecked(MINTER),
-            Addr::unchecked(COLLECTION_CONTRACT),
-            &mint_msg,
-            &[],
-        )
-        .unwrap();
-    }
-
-    fn join_group(app: &mut App, sender: String, token_id: String) {
-        let msg = to_binary("This is unused").unwrap();
-
-        let send_nft_msg = Cw721ExecuteMsg::SendNft::<Extension, Extension> {
-            contract: SG_NFT_GROUP_CONTRACT.to_string(),
-            token_id,
-            msg,
-        };
-        app.execute_contract(
-            Addr::unchecked(sender),
-            Addr::unchecked(COLLECTION_CONTRACT),
-            &send_nft_msg,
-            &[],
-        )
-        .unwrap();
-    }
-
-    fn mint_and_join_nft_group(app: &mut App, members: Vec<Member>) {
-        for member in members {
-            for i in 0..member.weight {
-                let token_id = format!("{}/{}", member.clone().addr, i);
-                mint_into_collection(app, member.clone().addr, token_id.clone());
-                join_group(app, member.clone().addr, token_id);
-            }
-        }
-    }
-
-    #[track_caller]
-    fn instantiate_dao(
-        app: &mut App,
-        threshold: Threshold,
-        max_voting_period: Duration,
-        executor: Option<crate::state::Executor>,
-    ) -> Addr {
-        let dao_id = app.store_code(contract_nft_dao());
-        let init_group = sg_nft_group_init_info(app);
-        let init_msg: sg_nft_group::msg::InstantiateMsg = from_binary(&init_group.msg).unwrap();
-        let group_addr = app
-            .instantiate_contract(
-                init_group.code_id,
-                Addr::unchecked(OWNER),
-                &init_msg,
-                &[],
-                init_group.label,
-                None,
-            )
-            .unwrap();
-        let msg = InstantiateMsg {
-            name: "name".to_string(),
-            description: "description".to_string(),
-            image: "image".to_string(),
-            group: Group::Cw4Address(group_addr.to_string()),
-            threshold,
-            max_voting_period,
-            executor,
-        };
-        mint_and_join_nft_group(app, members());
-        app.instantiate_contract(dao_id, Addr::unchecked(OWNER), &msg, &[], "dao", None)
-            .unwrap()
-    }
-
-    // this will set up both contracts, instantiating the group with
-    // all voters defined above, and the multisig pointing to it and given threshold criteria.
-    // Returns multisig address.
-    #[track_caller]
-    fn setup_test_case_fixed(
-        app: &mut App,
-        weight_needed: u64,
-        max_voting_period: Duration,
-        init_funds: Vec<Coin>,
-    ) -> Addr {
-        setup_test_case(
-            app,
-            Threshold::AbsoluteCount {
-                weight: weight_needed,
-            },
-            max_voting_period,
-            init_funds,
-            None,
-        )
-    }
-
-    #[track_caller]
-    fn setup_test_case(
-        app: &mut App,
-        threshold: Threshold,
-        max_voting_period: Duration,
-        init_funds: Vec<Coin>,
-        executor: Option<crate::state::Executor>,
-    ) -> Addr {
-        let dao_addr = instantiate_dao(app, threshold, max_voting_period, executor);
-        app.update_block(next_block);
-
-        // Bonus: set some funds on the multisig contract for future proposals
-        if !init_funds.is_empty() {
-            app.send_tokens(Addr::unchecked(OWNER), dao_addr.clone(), &init_funds)
-                .unwrap();
-        }
-        dao_addr
-    }
-
-    fn proposal_info() -> (Vec<CosmosMsg<Empty>>, String, String) {
-        let bank_msg = BankMsg::Send {
-            to_address: SOMEBODY.into(),
-            amount: coins(1, "BTC"),
-        };
-        let msgs = vec![bank_msg.into()];
-        let title = "Pay somebody".to_string();
-        let description = "Do I pay her?".to_string();
-        (msgs, title, description)
-    }
-
-    fn pay_somebody_proposal() -> ExecuteMsg {
-        let (msgs, title, description) = proposal_info();
-        ExecuteMsg::Propose {
-            title,
-            description,
-            msgs,
-            latest: None,
-        }
-    }
+    use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+    use ripemd::Ripemd160;
+    use sg_daos::{Admin, ContractInstantiateMsg, RagequitMsg};
+    use sg_std::{
+        create_claim_for_msg, create_fund_community_pool_msg, create_fund_fairburn_pool_msg,
+        ClaimAction, CosmosMsg,
+    };
+    use sha2::{Digest, Sha256};
 
     #[test]
     fn test_instantiate_existing_group() {
@@ -300,7 +79,11 @@ mod tests {
                         quorum: Decimal::percent(1),
                     },
                     max_voting_period,
+                    min_voting_period: None,
+                    grace_period: None,
                     executor: None,
+                    parent: None,
+                    registry: None,
                 },
                 &[],
                 "zero required weight",
@@ -324,7 +107,11 @@ mod tests {
                     group: Group::Cw4Address(group_addr.to_string()),
                     threshold: Threshold::AbsoluteCount { weight: 100 },
                     max_voting_period,
+                    min_voting_period: None,
+                    grace_period: None,
                     executor: None,
+                    parent: None,
+                    registry: None,
                 },
                 &[],
                 "high required weight",
@@ -349,7 +136,11 @@ mod tests {
                     group: Group::Cw4Address(group_addr.to_string()),
                     threshold: Threshold::AbsoluteCount { weight: 1 },
                     max_voting_period,
+                    min_voting_period: None,
+                    grace_period: None,
                     executor: None,
+                    parent: None,
+                    registry: None,
                 },
                 &[],
                 "all good",
@@ -421,7 +212,11 @@ mod tests {
                 quorum: Decimal::percent(1),
             },
             max_voting_period,
+            min_voting_period: None,
+            grace_period: None,
             executor: None,
+            parent: None,
+            registry: None,
         };
         let err = app
             .instantiate_contract(
@@ -446,7 +241,11 @@ mod tests {
             group: Group::Cw4Instantiate(init_group),
             threshold: Threshold::AbsoluteCount { weight: 1 },
             max_voting_period,
+            min_voting_period: None,
+            grace_period: None,
             executor: None,
+            parent: None,
+            registry: None,
         };
         let dao_addr = app
             .instantiate_contract(
@@ -545,7 +344,11 @@ mod tests {
             title: "Rewarding somebody".to_string(),
             description: "Do we reward her?".to_string(),
             msgs,
+            actions: vec![],
+            metadata: None,
             latest: Some(Expiration::AtHeight(123456)),
+            execution_mode: None,
+            proposer_vote: None,
         };
         let err = app
             .execute_contract(
@@ -586,15 +389,6 @@ mod tests {
         );
     }
 
-    fn expire(voting_period: Duration) -> impl Fn(&mut BlockInfo) {
-        move |block: &mut BlockInfo| {
-            match voting_period {
-                Duration::Time(duration) => block.time = block.time.plus_seconds(duration + 1),
-                Duration::Height(duration) => block.height += duration + 1,
-            };
-        }
-    }
-
     #[test]
     fn test_proposal_queries() {
         let init_funds = coins(10, "BTC");
@@ -638,6 +432,7 @@ mod tests {
         let list_query = QueryMsg::ListProposals {
             start_after: None,
             limit: None,
+            category: None,
         };
         let res: ProposalListResponse =
             app.wrap().query_wasm_smart(&dao_addr, &list_query).unwrap();
@@ -672,11 +467,15 @@ mod tests {
         let (msgs, title, description) = proposal_info();
         let expected = ProposalResponse {
             id: proposal_id3,
+            proposer: Addr::unchecked(VOTER2),
             title,
             description,
             msgs,
+            actions: vec![],
+            metadata: None,
             expires: voting_period.after(&proposed_at),
             status: Status::Open,
+            execution_status: None,
             threshold: ThresholdResponse::ThresholdQuorum {
                 total_weight: 24,
                 threshold: Decimal::percent(80),
@@ -740,6 +539,7 @@ mod tests {
         let yes_vote = ExecuteMsg::Vote {
             proposal_id,
             vote: Vote::Yes,
+            rationale: None,
         };
         let err = app
             .execute_contract(Addr::unchecked(SOMEBODY), dao_addr.clone(), &yes_vote, &[])
@@ -775,6 +575,7 @@ mod tests {
         let no_vote = ExecuteMsg::Vote {
             proposal_id,
             vote: Vote::No,
+            rationale: None,
         };
         let _ = app
             .execute_contract(Addr::unchecked(VOTER2), dao_addr.clone(), &no_vote, &[])
@@ -784,6 +585,7 @@ mod tests {
         let veto_vote = ExecuteMsg::Vote {
             proposal_id,
             vote: Vote::Veto,
+            rationale: None,
         };
         let _ = app
             .execute_contract(Addr::unchecked(VOTER3), dao_addr.clone(), &veto_vote, &[])
@@ -846,7 +648,8 @@ mod tests {
                 proposal_id,
                 voter: OWNER.into(),
                 vote: Vote::Yes,
-                weight: 1
+                weight: 1,
+                rationale: None,
             }
         );
 
@@ -862,7 +665,8 @@ mod tests {
                 proposal_id,
                 voter: VOTER2.into(),
                 vote: Vote::No,
-                weight: 2
+                weight: 2,
+                rationale: None,
             }
         );
 
@@ -887,6 +691,7 @@ mod tests {
         let no_vote = ExecuteMsg::Vote {
             proposal_id,
             vote: Vote::No,
+            rationale: None,
         };
         let _ = app
             .execute_contract(Addr::unchecked(VOTER2), dao_addr.clone(), &no_vote, &[])
@@ -911,6 +716,7 @@ mod tests {
         let yes_vote = ExecuteMsg::Vote {
             proposal_id,
             vote: Vote::Yes,
+            rationale: None,
         };
         let res = app
             .execute_contract(Addr::unchecked(VOTER5), dao_addr, &yes_vote, &[])
@@ -966,6 +772,7 @@ mod tests {
         let vote = ExecuteMsg::Vote {
             proposal_id,
             vote: Vote::Yes,
+            rationale: None,
         };
         let res = app
             .execute_contract(Addr::unchecked(VOTER4), dao_addr.clone(), &vote, &[])
@@ -1053,6 +860,7 @@ mod tests {
         let vote = ExecuteMsg::Vote {
             proposal_id,
             vote: Vote::Yes,
+            rationale: None,
         };
         app.execute_contract(Addr::unchecked(VOTER4), dao_addr.clone(), &vote, &[])
             .unwrap();
@@ -1108,6 +916,7 @@ mod tests {
         let vote = ExecuteMsg::Vote {
             proposal_id,
             vote: Vote::Yes,
+            rationale: None,
         };
         app.execute_contract(Addr::unchecked(VOTER4), dao_addr.clone(), &vote, &[])
             .unwrap();
@@ -1177,6 +986,7 @@ mod tests {
         let vote = ExecuteMsg::Vote {
             proposal_id,
             vote: Vote::Yes,
+            rationale: None,
         };
         let res = app
             .execute_contract(Addr::unchecked(VOTER3), dao_addr.clone(), &vote, &[])
@@ -1271,6 +1081,188 @@ mod tests {
         assert_eq!(ContractError::WrongCloseStatus {}, err.downcast().unwrap());
     }
 
+    #[test]
+    fn proposal_rejected_once_it_cannot_pass() {
+        // total weight is 24, the proposer votes yes with 1 and VOTER5 votes with 5,
+        // leaving at most 19 yes votes
+        let cases = vec![
+            (Threshold::AbsoluteCount { weight: 20 }, Vote::Abstain),
+            (
+                Threshold::AbsolutePercentage {
+                    percentage: Decimal::percent(80),
+                },
+                Vote::Veto,
+            ),
+            (
+                Threshold::ThresholdQuorum {
+                    threshold: Decimal::percent(80),
+                    quorum: Decimal::percent(20),
+                },
+                Vote::Veto,
+            ),
+        ];
+
+        for (threshold, ballot) in cases {
+            let mut app = mock_app(&[]);
+            let voting_period = Duration::Time(2000000);
+            let dao_addr = setup_test_case(&mut app, threshold, voting_period, vec![], None);
+
+            let proposal_id = propose(&mut app, &dao_addr, OWNER, proposal_info().0);
+            assert_eq!(status(&app, &dao_addr, proposal_id), Status::Open);
+
+            // still possible to pass
+            let res = vote(&mut app, &dao_addr, VOTER1, proposal_id, Vote::No);
+            assert_eq!(res.custom_attrs(1)[3], ("status", "Open"));
+            let err = app
+                .execute_contract(
+                    Addr::unchecked(SOMEBODY),
+                    dao_addr.clone(),
+                    &ExecuteMsg::Close { proposal_id },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(ContractError::NotExpired {}, err.downcast().unwrap());
+
+            // rejected as soon as the threshold is out of reach, before expiration
+            let res = vote(&mut app, &dao_addr, VOTER5, proposal_id, ballot);
+            assert_eq!(res.custom_attrs(1)[3], ("status", "Rejected"));
+            assert_eq!(status(&app, &dao_addr, proposal_id), Status::Rejected);
+
+            // even the remaining votes cannot pass it anymore
+            vote(&mut app, &dao_addr, VOTER4, proposal_id, Vote::Yes);
+            let err = app
+                .execute_contract(
+                    Addr::unchecked(SOMEBODY),
+                    dao_addr.clone(),
+                    &ExecuteMsg::Execute { proposal_id },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                ContractError::WrongExecuteStatus {},
+                err.downcast().unwrap()
+            );
+
+            // it can be closed before it expires, but only once
+            let closing = ExecuteMsg::Close { proposal_id };
+            app.execute_contract(Addr::unchecked(SOMEBODY), dao_addr.clone(), &closing, &[])
+                .unwrap();
+            let err = app
+                .execute_contract(Addr::unchecked(SOMEBODY), dao_addr, &closing, &[])
+                .unwrap_err();
+            assert_eq!(ContractError::WrongCloseStatus {}, err.downcast().unwrap());
+        }
+    }
+
+    #[test]
+    fn min_voting_period_delays_passing() {
+        let init_funds = coins(10, "BTC");
+        let mut app = mock_app(&init_funds);
+
+        let max_voting_period = Duration::Time(2000000);
+        let min_voting_period = Duration::Time(86400);
+
+        // minimum voting period cannot be longer than the maximum
+        let dao_id = app.store_code(contract_nft_dao());
+        let err = app
+            .instantiate_contract(
+                dao_id,
+                Addr::unchecked(OWNER),
+                &InstantiateMsg {
+                    name: "name".to_string(),
+                    description: "description".to_string(),
+                    image: "image".to_string(),
+                    group: Group::Cw4Address(SOMEBODY.to_string()),
+                    threshold: Threshold::AbsoluteCount { weight: 12 },
+                    max_voting_period,
+                    min_voting_period: Some(Duration::Time(2000001)),
+                    grace_period: None,
+                    executor: None,
+                    parent: None,
+                    registry: None,
+                },
+                &[],
+                "dao",
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidMinVotingPeriod {},
+            err.downcast().unwrap()
+        );
+
+        let dao_addr = instantiate_dao(
+            &mut app,
+            Threshold::AbsoluteCount { weight: 12 },
+            max_voting_period,
+            Some(min_voting_period),
+            None,
+            None,
+        );
+        app.update_block(next_block);
+        app.send_tokens(Addr::unchecked(OWNER), dao_addr.clone(), &init_funds)
+            .unwrap();
+
+        // proposals must stay open at least for the minimum voting period
+        let (msgs, title, description) = proposal_info();
+        let err = app
+            .execute_contract(
+                Addr::unchecked(VOTER4),
+                dao_addr.clone(),
+                &ExecuteMsg::Propose {
+                    title,
+                    description,
+                    msgs,
+                    actions: vec![],
+                    metadata: None,
+                    latest: Some(Expiration::AtTime(app.block_info().time.plus_seconds(3600))),
+                    execution_mode: None,
+                    proposer_vote: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::WrongExpiration {}, err.downcast().unwrap());
+
+        // reaches the threshold on creation, but stays open
+        let proposal_id = propose(&mut app, &dao_addr, VOTER4, proposal_info().0);
+        assert_eq!(status(&app, &dao_addr, proposal_id), Status::Open);
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked(SOMEBODY),
+                dao_addr.clone(),
+                &ExecuteMsg::Execute { proposal_id },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::WrongExecuteStatus {},
+            err.downcast().unwrap()
+        );
+
+        // passes once the minimum voting period is over
+        app.update_block(expire(min_voting_period));
+        assert_eq!(status(&app, &dao_addr, proposal_id), Status::Passed);
+
+        let list: ProposalListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::ListProposals {
+                    start_after: None,
+                    limit: None,
+                    category: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(list.proposals[0].status, Status::Passed);
+
+        execute_proposal(&mut app, &dao_addr, proposal_id);
+        let some_bal = app.wrap().query_balance(SOMEBODY, "BTC").unwrap();
+        assert_eq!(some_bal, coin(1, "BTC"));
+    }
+
     #[test]
     fn quorum_enforced_even_if_absolute_threshold_met() {
         let init_funds = coins(10, "BTC");
@@ -1311,6 +1303,7 @@ mod tests {
         let yes_vote = ExecuteMsg::Vote {
             proposal_id,
             vote: Vote::Yes,
+            rationale: None,
         };
         app.execute_contract(Addr::unchecked(VOTER4), dao_addr.clone(), &yes_vote, &[])
             .unwrap();
@@ -1321,65 +1314,15 @@ mod tests {
         let no_vote = ExecuteMsg::Vote {
             proposal_id,
             vote: Vote::No,
+            rationale: None,
         };
         app.execute_contract(Addr::unchecked(VOTER3), dao_addr.clone(), &no_vote, &[])
             .unwrap();
         assert_eq!(prop_status(&app), Status::Passed);
     }
 
-    // NFT tests ------------------------------------------------------------------
-
-    const TOKEN_ID: &str = "token0001";
-    const MINTER: &str = "minter";
-
-    #[track_caller]
-    fn instantiate_collection(app: &mut App) -> Addr {
-        let collection_code_id = app.store_code(contract_cw721());
-        let msg = Cw721InstantiateMsg {
-            name: "My NFTs".to_string(),
-            symbol: "NFT".to_string(),
-            minter: MINTER.into(),
-        };
-        app.instantiate_contract(
-            collection_code_id,
-            Addr::unchecked(OWNER),
-            &msg,
-            &[],
-            "collection",
-            None,
-        )
-        .unwrap()
-    }
-
-    #[track_caller]
-    fn setup_test_collection(app: &mut App) -> Addr {
-        let collection_addr = instantiate_collection(app);
-        app.update_block(next_block);
-
-        // mint an NFT
-        let token_id = TOKEN_ID.to_string();
-        let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
-
-        let mint_msg = Cw721ExecuteMsg::Mint::<Extension, Extension>(MintMsg::<Extension> {
-            token_id,
-            owner: OWNER.into(),
-            token_uri: Some(token_uri),
-            extension: None,
-        });
-
-        app.execute_contract(
-            Addr::unchecked(MINTER),
-            collection_addr.clone(),
-            &mint_msg,
-            &[],
-        )
-        .unwrap();
-
-        collection_addr
-    }
-
     #[test]
-    fn transfer_nft_to_dao_works() {
+    fn vote_rationale_is_recorded() {
         let init_funds = coins(10, "BTC");
         let mut app = mock_app(&init_funds);
 
@@ -1390,154 +1333,1524 @@ mod tests {
         let voting_period = Duration::Time(2000000);
         let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
 
-        let collection_addr = setup_test_collection(&mut app);
-
-        // ensure we have cash to cover the proposal
-        let contract_bal = app.wrap().query_balance(&dao_addr, "BTC").unwrap();
-        assert_eq!(contract_bal, coin(10, "BTC"));
-
-        // transfer NFT from collection to DAO
-        let transfer_msg: Cw721ExecuteMsg<Extension, Extension> = Cw721ExecuteMsg::TransferNft {
-            recipient: dao_addr.to_string(),
-            token_id: TOKEN_ID.to_string(),
-        };
-        app.execute_contract(
-            Addr::unchecked(OWNER),
-            collection_addr.clone(),
-            &transfer_msg,
-            &[],
-        )
-        .unwrap();
+        let proposal_id = propose(&mut app, &dao_addr, OWNER, proposal_info().0);
 
-        let res: OwnerOfResponse = app
-            .wrap()
-            .query_wasm_smart(
-                collection_addr,
-                &Cw721QueryMsg::OwnerOf {
-                    token_id: TOKEN_ID.to_string(),
-                    include_expired: None,
+        // rationale is length limited
+        let err = app
+            .execute_contract(
+                Addr::unchecked(VOTER1),
+                dao_addr.clone(),
+                &ExecuteMsg::Vote {
+                    proposal_id,
+                    vote: Vote::Yes,
+                    rationale: Some("a".repeat(MAX_RATIONALE_LENGTH + 1)),
                 },
+                &[],
             )
-            .unwrap();
-        assert_eq!(dao_addr, res.owner);
-    }
+            .unwrap_err();
+        assert_eq!(
+            ContractError::RationaleTooLong {
+                max: MAX_RATIONALE_LENGTH
+            },
+            err.downcast().unwrap()
+        );
 
-    #[track_caller]
-    fn propose_pass_execute(app: &mut App, dao_addr: Addr, msg: WasmMsg) {
-        // create proposal
+        let rationale = "Somebody did great work".to_string();
         let res = app
             .execute_contract(
-                Addr::unchecked(OWNER),
+                Addr::unchecked(VOTER1),
                 dao_addr.clone(),
-                &ExecuteMsg::Propose {
-                    title: "title".to_string(),
-                    description: "description".to_string(),
-                    msgs: vec![CosmosMsg::Wasm(msg)],
-                    latest: None,
+                &ExecuteMsg::Vote {
+                    proposal_id,
+                    vote: Vote::Yes,
+                    rationale: Some(rationale.clone()),
                 },
                 &[],
             )
             .unwrap();
-        let proposal_id: u64 = res.custom_attrs(1)[2].value.parse().unwrap();
-
-        // Vote
-        let vote = ExecuteMsg::Vote {
-            proposal_id,
-            vote: Vote::Yes,
-        };
-        let res = app
-            .execute_contract(Addr::unchecked(VOTER4), dao_addr.clone(), &vote, &[])
-            .unwrap();
         assert_eq!(
             res.custom_attrs(1),
             [
                 ("action", "vote"),
-                ("sender", VOTER4),
+                ("sender", VOTER1),
                 ("proposal_id", proposal_id.to_string().as_str()),
-                ("status", "Passed"),
+                ("status", "Open"),
+                ("rationale", rationale.as_str()),
             ],
         );
 
-        // In passing: Try to close Passed fails
-        let closing = ExecuteMsg::Close { proposal_id };
-        let err = app
-            .execute_contract(Addr::unchecked(OWNER), dao_addr.clone(), &closing, &[])
-            .unwrap_err();
-        assert_eq!(ContractError::WrongCloseStatus {}, err.downcast().unwrap());
+        let vote: VoteResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::Vote {
+                    proposal_id,
+                    voter: VOTER1.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(vote.vote.unwrap().rationale, Some(rationale.clone()));
 
-        // Execute
-        let execution = ExecuteMsg::Execute { proposal_id };
-        let res = app
-            .execute_contract(Addr::unchecked(SOMEBODY), dao_addr.clone(), &execution, &[])
+        let votes: VoteListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::ListVotes {
+                    proposal_id,
+                    start_after: None,
+                    limit: None,
+                },
+            )
             .unwrap();
+        let rationales: Vec<_> = votes
+            .votes
+            .into_iter()
+            .map(|v| (v.voter, v.rationale))
+            .collect();
+        assert_eq!(
+            rationales,
+            vec![
+                (OWNER.to_string(), None),
+                (VOTER1.to_string(), Some(rationale)),
+            ]
+        );
+    }
+
+    struct Wallet {
+        key: SigningKey,
+        pubkey: Vec<u8>,
+        addr: String,
+    }
+
+    fn wallet(secret: u8) -> Wallet {
+        let key = SigningKey::from_bytes(&[secret; 32]).unwrap();
+        // compressed public key
+        let pubkey = key.verifying_key().to_bytes().to_vec();
+        let hash = Ripemd160::digest(Sha256::digest(&pubkey));
+        let addr = bech32::encode(BECH32_PREFIX, hash.to_base32(), Variant::Bech32).unwrap();
+        Wallet { key, pubkey, addr }
+    }
+
+    fn sign_vote(wallet: &Wallet, payload: SignedVotePayload) -> SignedVote {
+        let signature: Signature = wallet.key.sign(&to_vec(&payload).unwrap());
+        SignedVote {
+            payload,
+            pubkey: wallet.pubkey.clone().into(),
+            signature: signature.as_ref().to_vec().into(),
+        }
+    }
+
+    #[test]
+    fn signed_votes_work() {
+        let init_funds = coins(10, "BTC");
+        let mut app = mock_app(&init_funds);
+
+        let threshold = Threshold::ThresholdQuorum {
+            threshold: Decimal::percent(51),
+            quorum: Decimal::percent(1),
+        };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
+
+        // the voters keep their NFTs on hardware wallets
+        let voter = wallet(1);
+        let outsider = wallet(2);
+        mint_and_join_nft_group(&mut app, vec![member(voter.addr.clone(), 24)]);
+        app.update_block(next_block);
+
+        let proposal_id = propose(&mut app, &dao_addr, OWNER, proposal_info().0);
+
+        let payload = SignedVotePayload {
+            contract: dao_addr.to_string(),
+            proposal_id,
+            vote: Vote::Yes,
+            nonce: 0,
+        };
+        let submit = |app: &mut App, votes: Vec<SignedVote>| {
+            app.execute_contract(
+                Addr::unchecked(SOMEBODY),
+                dao_addr.clone(),
+                &ExecuteMsg::SubmitSignedVotes { votes },
+                &[],
+            )
+        };
+
+        // signature must match the payload
+        let mut tampered = sign_vote(&voter, payload.clone());
+        tampered.payload.vote = Vote::No;
+        let err = submit(&mut app, vec![tampered]).unwrap_err();
+        assert_eq!(ContractError::InvalidSignature {}, err.downcast().unwrap());
+
+        // votes for another contract are rejected
+        let mut other_contract = payload.clone();
+        other_contract.contract = "other".to_string();
+        let err = submit(&mut app, vec![sign_vote(&voter, other_contract)]).unwrap_err();
+        assert_eq!(ContractError::WrongContract {}, err.downcast().unwrap());
+
+        // non-members cannot vote
+        let err = submit(&mut app, vec![sign_vote(&outsider, payload.clone())]).unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        // anyone can relay a valid signed vote
+        let res = submit(&mut app, vec![sign_vote(&voter, payload.clone())]).unwrap();
         assert_eq!(
             res.custom_attrs(1),
             [
-                ("action", "execute"),
+                ("action", "submit_signed_votes"),
                 ("sender", SOMEBODY),
-                ("proposal_id", proposal_id.to_string().as_str()),
+                ("voter", voter.addr.as_str()),
             ],
         );
 
-        // Trying to execute something that was already executed fails
-        let err = app
-            .execute_contract(Addr::unchecked(SOMEBODY), dao_addr, &execution, &[])
-            .unwrap_err();
+        let vote: VoteResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::Vote {
+                    proposal_id,
+                    voter: voter.addr.clone(),
+                },
+            )
+            .unwrap();
         assert_eq!(
-            ContractError::WrongExecuteStatus {},
+            vote.vote.unwrap(),
+            VoteInfo {
+                proposal_id,
+                voter: voter.addr.clone(),
+                vote: Vote::Yes,
+                weight: 24,
+                rationale: None,
+            }
+        );
+        assert_eq!(status(&app, &dao_addr, proposal_id), Status::Passed);
+
+        // signed votes cannot be replayed
+        let err = submit(&mut app, vec![sign_vote(&voter, payload.clone())]).unwrap_err();
+        assert_eq!(
+            ContractError::InvalidNonce { expected: 1 },
             err.downcast().unwrap()
         );
+        let nonce: NonceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::Nonce {
+                    address: voter.addr.clone(),
+                },
+            )
+            .unwrap();
+        assert_eq!(nonce.nonce, 1);
+
+        // a fresh nonce still cannot vote twice
+        let err = submit(
+            &mut app,
+            vec![sign_vote(
+                &voter,
+                SignedVotePayload {
+                    nonce: 1,
+                    ..payload
+                },
+            )],
+        )
+        .unwrap_err();
+        assert_eq!(ContractError::AlreadyVoted {}, err.downcast().unwrap());
     }
 
     #[test]
-    fn proposal_nft_transfer_works() {
+    fn delegator_vote_can_unpass_proposal() {
         let mut app = mock_app(&[]);
 
-        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let threshold = Threshold::AbsoluteCount { weight: 10 };
         let voting_period = Duration::Time(2000000);
         let dao_addr = setup_test_case(&mut app, threshold, voting_period, vec![], None);
 
-        let collection_addr = setup_test_collection(&mut app);
+        // VOTER3 votes with 3 + 2 + 5
+        for delegator in [VOTER2, VOTER5] {
+            app.execute_contract(
+                Addr::unchecked(delegator),
+                dao_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    delegate: VOTER3.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+        }
+        app.update_block(next_block);
 
-        // transfer NFT from collection to DAO
-        let transfer_msg: Cw721ExecuteMsg<Extension, Extension> = Cw721ExecuteMsg::TransferNft {
-            recipient: dao_addr.to_string(),
-            token_id: TOKEN_ID.into(),
+        let proposal_id = propose(&mut app, &dao_addr, OWNER, proposal_info().0);
+
+        // 1 + 10 yes passes the proposal
+        vote(&mut app, &dao_addr, VOTER3, proposal_id, Vote::Yes);
+        assert_eq!(status(&app, &dao_addr, proposal_id), Status::Passed);
+
+        // VOTER5 takes back 5, leaving 6 yes
+        vote(&mut app, &dao_addr, VOTER5, proposal_id, Vote::No);
+        assert_eq!(status(&app, &dao_addr, proposal_id), Status::Open);
+        let err = app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                dao_addr.clone(),
+                &ExecuteMsg::Execute { proposal_id },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::WrongExecuteStatus {},
+            err.downcast().unwrap()
+        );
+
+        // passes again with enough direct votes
+        vote(&mut app, &dao_addr, VOTER1, proposal_id, Vote::Yes);
+        vote(&mut app, &dao_addr, VOTER4, proposal_id, Vote::Yes);
+        assert_eq!(status(&app, &dao_addr, proposal_id), Status::Passed);
+    }
+
+    #[test]
+    fn delegated_votes_count_unless_delegator_votes() {
+        let init_funds = coins(10, "BTC");
+        let mut app = mock_app(&init_funds);
+
+        let threshold = Threshold::ThresholdQuorum {
+            threshold: Decimal::percent(51),
+            quorum: Decimal::percent(1),
         };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
+
+        // cannot delegate to yourself
+        let err = app
+            .execute_contract(
+                Addr::unchecked(VOTER3),
+                dao_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    delegate: VOTER3.to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::SelfDelegation {}, err.downcast().unwrap());
+
+        // only members can delegate
+        let err = app
+            .execute_contract(
+                Addr::unchecked(SOMEBODY),
+                dao_addr.clone(),
+                &ExecuteMsg::Delegate {
+                    delegate: VOTER3.to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        // VOTER2 and VOTER5 delegate to VOTER3
+        for delegator in [VOTER2, VOTER5] {
+            let res = app
+                .execute_contract(
+                    Addr::unchecked(delegator),
+                    dao_addr.clone(),
+                    &ExecuteMsg::Delegate {
+                        delegate: VOTER3.to_string(),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                res.custom_attrs(1),
+                [
+                    ("action", "delegate"),
+                    ("sender", delegator),
+                    ("delegate", VOTER3),
+                ],
+            );
+        }
+        app.update_block(next_block);
+
+        let power: VotingPowerResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::VotingPower {
+                    address: VOTER3.to_string(),
+                    at_height: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            power,
+            VotingPowerResponse {
+                weight: 3,
+                delegated: 7,
+                total: 10,
+            }
+        );
+
+        let delegations: DelegationListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::ListDelegations {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            delegations.delegations,
+            vec![
+                Delegation {
+                    delegator: Addr::unchecked(VOTER2),
+                    delegate: Addr::unchecked(VOTER3),
+                },
+                Delegation {
+                    delegator: Addr::unchecked(VOTER5),
+                    delegate: Addr::unchecked(VOTER3),
+                },
+            ]
+        );
+
+        // create proposal with 1 vote power
+        let proposal_id = propose(&mut app, &dao_addr, OWNER, proposal_info().0);
+
+        // delegations made after the proposal started are ignored
         app.execute_contract(
-            Addr::unchecked(OWNER),
-            collection_addr.clone(),
-            &transfer_msg,
+            Addr::unchecked(VOTER1),
+            dao_addr.clone(),
+            &ExecuteMsg::Delegate {
+                delegate: VOTER4.to_string(),
+            },
             &[],
         )
         .unwrap();
 
-        propose_pass_execute(
-            &mut app,
-            dao_addr,
-            WasmMsg::Execute {
-                contract_addr: collection_addr.to_string(),
-                msg: to_binary(&Cw721ExecuteMsg::TransferNft::<Extension, Extension> {
-                    recipient: SOMEBODY.into(),
-                    token_id: TOKEN_ID.into(),
-                })
-                .unwrap(),
-                funds: vec![],
-            },
+        let vote_weight = |app: &App, voter: &str| -> u64 {
+            let vote: VoteResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &dao_addr,
+                    &QueryMsg::Vote {
+                        proposal_id,
+                        voter: voter.to_string(),
+                    },
+                )
+                .unwrap();
+            vote.vote.unwrap().weight
+        };
+
+        // delegate votes with the combined weight
+        vote(&mut app, &dao_addr, VOTER3, proposal_id, Vote::Yes);
+        assert_eq!(vote_weight(&app, VOTER3), 10);
+        assert_eq!(get_tally(&app, dao_addr.as_ref(), proposal_id), 11);
+
+        // a delegator voting directly takes back their weight
+        vote(&mut app, &dao_addr, VOTER2, proposal_id, Vote::No);
+        assert_eq!(vote_weight(&app, VOTER3), 8);
+        assert_eq!(vote_weight(&app, VOTER2), 2);
+        assert_eq!(get_tally(&app, dao_addr.as_ref(), proposal_id), 9);
+
+        // VOTER1's delegation came too late, so VOTER4 only votes with their own weight
+        let res = vote(&mut app, &dao_addr, VOTER4, proposal_id, Vote::Yes);
+        assert_eq!(
+            res.custom_attrs(1),
+            [
+                ("action", "vote"),
+                ("sender", VOTER4),
+                ("proposal_id", proposal_id.to_string().as_str()),
+                ("status", "Passed"),
+            ],
         );
+        assert_eq!(vote_weight(&app, VOTER4), 12);
 
-        // verify NFT was transfered
-        let res: OwnerOfResponse = app
+        // VOTER1 can still vote directly
+        vote(&mut app, &dao_addr, VOTER1, proposal_id, Vote::Yes);
+        assert_eq!(get_tally(&app, dao_addr.as_ref(), proposal_id), 22);
+
+        // undelegate
+        app.execute_contract(
+            Addr::unchecked(VOTER5),
+            dao_addr.clone(),
+            &ExecuteMsg::Undelegate {},
+            &[],
+        )
+        .unwrap();
+        let delegation: DelegationResponse = app
             .wrap()
             .query_wasm_smart(
-                collection_addr,
-                &Cw721QueryMsg::OwnerOf {
-                    token_id: TOKEN_ID.to_string(),
-                    include_expired: None,
+                &dao_addr,
+                &QueryMsg::Delegation {
+                    delegator: VOTER5.to_string(),
                 },
             )
             .unwrap();
-        assert_eq!(SOMEBODY, res.owner);
+        assert_eq!(delegation.delegate, None);
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked(VOTER5),
+                dao_addr.clone(),
+                &ExecuteMsg::Undelegate {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::NoDelegation {}, err.downcast().unwrap());
+
+        let power: VotingPowerResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::VotingPower {
+                    address: VOTER3.to_string(),
+                    at_height: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(power.delegated, 2);
+    }
+
+    // NFT tests ------------------------------------------------------------------
+
+    #[test]
+    fn transfer_nft_to_dao_works() {
+        let init_funds = coins(10, "BTC");
+        let mut app = mock_app(&init_funds);
+
+        let threshold = Threshold::ThresholdQuorum {
+            threshold: Decimal::percent(51),
+            quorum: Decimal::percent(1),
+        };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
+
+        let collection_addr = setup_test_collection(&mut app);
+
+        // ensure we have cash to cover the proposal
+        let contract_bal = app.wrap().query_balance(&dao_addr, "BTC").unwrap();
+        assert_eq!(contract_bal, coin(10, "BTC"));
+
+        // transfer NFT from collection to DAO
+        let transfer_msg: Cw721ExecuteMsg<Extension, Extension> = Cw721ExecuteMsg::TransferNft {
+            recipient: dao_addr.to_string(),
+            token_id: TOKEN_ID.to_string(),
+        };
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            collection_addr.clone(),
+            &transfer_msg,
+            &[],
+        )
+        .unwrap();
+
+        let res: OwnerOfResponse = app
+            .wrap()
+            .query_wasm_smart(
+                collection_addr,
+                &Cw721QueryMsg::OwnerOf {
+                    token_id: TOKEN_ID.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(dao_addr, res.owner);
+    }
+
+    #[test]
+    fn proposal_nft_transfer_works() {
+        let mut app = mock_app(&[]);
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, vec![], None);
+
+        let collection_addr = setup_test_collection(&mut app);
+
+        // transfer NFT from collection to DAO
+        let transfer_msg: Cw721ExecuteMsg<Extension, Extension> = Cw721ExecuteMsg::TransferNft {
+            recipient: dao_addr.to_string(),
+            token_id: TOKEN_ID.into(),
+        };
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            collection_addr.clone(),
+            &transfer_msg,
+            &[],
+        )
+        .unwrap();
+
+        propose_pass_execute(
+            &mut app,
+            dao_addr,
+            WasmMsg::Execute {
+                contract_addr: collection_addr.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft::<Extension, Extension> {
+                    recipient: SOMEBODY.into(),
+                    token_id: TOKEN_ID.into(),
+                })
+                .unwrap(),
+                funds: vec![],
+            },
+        );
+
+        // verify NFT was transfered
+        let res: OwnerOfResponse = app
+            .wrap()
+            .query_wasm_smart(
+                collection_addr,
+                &Cw721QueryMsg::OwnerOf {
+                    token_id: TOKEN_ID.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(SOMEBODY, res.owner);
+    }
+
+    #[test]
+    fn ragequit_during_grace_period() {
+        let mut app = mock_app(&coins(2400, "ustars"));
+
+        let grace_period = Duration::Time(1000);
+        let dao_addr = instantiate_dao(
+            &mut app,
+            Threshold::AbsoluteCount { weight: 12 },
+            Duration::Time(2000000),
+            None,
+            Some(grace_period),
+            None,
+        );
+        app.update_block(next_block);
+        app.send_tokens(
+            Addr::unchecked(OWNER),
+            dao_addr.clone(),
+            &coins(2400, "ustars"),
+        )
+        .unwrap();
+        let token = instantiate_cw20(&mut app, "ONE", 240);
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            token.clone(),
+            &Cw20ExecuteMsg::Transfer {
+                recipient: dao_addr.to_string(),
+                amount: Uint128::new(240),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // a controversial proposal passes
+        let msgs = vec![BankMsg::Send {
+            to_address: SOMEBODY.to_string(),
+            amount: coins(100, "ustars"),
+        }
+        .into()];
+        let proposal_id = propose(&mut app, &dao_addr, OWNER, msgs);
+        vote(&mut app, &dao_addr, VOTER4, proposal_id, Vote::Yes);
+
+        // it cannot be executed during the grace period
+        let grace_ends = Expiration::AtTime(app.block_info().time.plus_seconds(1000));
+        let execution = ExecuteMsg::Execute { proposal_id };
+        let err = app
+            .execute_contract(Addr::unchecked(SOMEBODY), dao_addr.clone(), &execution, &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::GracePeriod { ends: grace_ends },
+            err.downcast().unwrap()
+        );
+
+        // only the group can pay out ragequits
+        let err = app
+            .execute_contract(
+                Addr::unchecked(VOTER5),
+                dao_addr.clone(),
+                &ExecuteMsg::Ragequit(RagequitMsg {
+                    member: VOTER5.to_string(),
+                    weight: 5,
+                    denoms: vec![Denom::Native("ustars".to_string())],
+                }),
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        // VOTER4 voted for the proposal, so cannot ragequit before it is executed
+        app.execute_contract(
+            Addr::unchecked(VOTER4),
+            Addr::unchecked("contract2"),
+            &Cw721ExecuteMsg::ApproveAll::<Extension, Extension> {
+                operator: SG_NFT_GROUP_CONTRACT.to_string(),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+        let err = app
+            .execute_contract(
+                Addr::unchecked(VOTER4),
+                Addr::unchecked(SG_NFT_GROUP_CONTRACT),
+                &sg_nft_group::msg::ExecuteMsg::Ragequit {
+                    token_ids: vec![format!("{}/0", VOTER4)],
+                    denoms: vec![Denom::Native("ustars".to_string())],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::PendingYesVote { proposal_id },
+            err.downcast().unwrap()
+        );
+
+        // VOTER5 leaves with 5 of 24 weight, the duplicate denom is only paid once
+        app.execute_contract(
+            Addr::unchecked(VOTER5),
+            Addr::unchecked("contract2"),
+            &Cw721ExecuteMsg::ApproveAll::<Extension, Extension> {
+                operator: SG_NFT_GROUP_CONTRACT.to_string(),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(VOTER5),
+            Addr::unchecked(SG_NFT_GROUP_CONTRACT),
+            &sg_nft_group::msg::ExecuteMsg::Ragequit {
+                token_ids: (0..5).map(|i| format!("{}/{}", VOTER5, i)).collect(),
+                denoms: vec![
+                    Denom::Native("ustars".to_string()),
+                    Denom::Native("ustars".to_string()),
+                    Denom::Cw20(token.clone()),
+                ],
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            app.wrap().query_balance(VOTER5, "ustars").unwrap(),
+            coin(500, "ustars")
+        );
+        let balance: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &token,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: VOTER5.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(balance.balance, Uint128::new(50));
+
+        // the membership is gone and the NFTs are in the treasury
+        let voter: VoterResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::Voter {
+                    address: VOTER5.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(voter.weight, Some(0));
+        let nfts: NftListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::ListNfts {
+                    collection: COLLECTION_CONTRACT.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(nfts.tokens.len(), 5);
+
+        // after the grace period the yes vote no longer holds VOTER4 back,
+        // even while the proposal is not executed
+        app.update_block(|block| block.time = block.time.plus_seconds(1000));
+        app.execute_contract(
+            Addr::unchecked(VOTER4),
+            Addr::unchecked(SG_NFT_GROUP_CONTRACT),
+            &sg_nft_group::msg::ExecuteMsg::Ragequit {
+                token_ids: vec![format!("{}/0", VOTER4)],
+                denoms: vec![Denom::Native("ustars".to_string())],
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            app.wrap().query_balance(VOTER4, "ustars").unwrap(),
+            coin(100, "ustars")
+        );
+
+        // and the proposal can be executed
+        execute_proposal(&mut app, &dao_addr, proposal_id);
+        assert_eq!(
+            app.wrap().query_balance(&dao_addr, "ustars").unwrap(),
+            coin(1700, "ustars")
+        );
+    }
+
+    #[test]
+    fn grace_period_starts_when_passing_on_propose() {
+        let mut app = mock_app(&[]);
+
+        let dao_addr = instantiate_dao(
+            &mut app,
+            Threshold::AbsoluteCount { weight: 12 },
+            Duration::Time(2000000),
+            None,
+            Some(Duration::Time(1000)),
+            None,
+        );
+        app.update_block(next_block);
+
+        // the vote of VOTER4 as proposer is enough to pass the proposal
+        let proposal_id = propose(&mut app, &dao_addr, VOTER4, vec![]);
+        assert_eq!(status(&app, &dao_addr, proposal_id), Status::Passed);
+
+        let grace_ends = Expiration::AtTime(app.block_info().time.plus_seconds(1000));
+        let execution = ExecuteMsg::Execute { proposal_id };
+        let err = app
+            .execute_contract(Addr::unchecked(SOMEBODY), dao_addr.clone(), &execution, &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::GracePeriod { ends: grace_ends },
+            err.downcast().unwrap()
+        );
+
+        app.update_block(|block| block.time = block.time.plus_seconds(1000));
+        execute_proposal(&mut app, &dao_addr, proposal_id);
+    }
+
+    #[test]
+    fn simulate_proposal_flags_failing_messages() {
+        let init_funds = coins(1000, "ustars");
+        let mut app = mock_app(&init_funds);
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
+
+        // the treasury holds TOKEN_ID but not token0002
+        let collection_addr = setup_test_collection(&mut app);
+        app.execute_contract(
+            Addr::unchecked(MINTER),
+            collection_addr.clone(),
+            &Cw721ExecuteMsg::Mint::<Extension, Extension>(MintMsg::<Extension> {
+                token_id: "token0002".to_string(),
+                owner: OWNER.to_string(),
+                token_uri: None,
+                extension: None,
+            }),
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            collection_addr.clone(),
+            &Cw721ExecuteMsg::SendNft::<Extension, Extension> {
+                contract: dao_addr.to_string(),
+                token_id: TOKEN_ID.to_string(),
+                msg: to_binary("unused").unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let send = |amount: u128| -> CosmosMsg {
+            BankMsg::Send {
+                to_address: SOMEBODY.to_string(),
+                amount: coins(amount, "ustars"),
+            }
+            .into()
+        };
+        let transfer_nft = |token_id: &str| -> CosmosMsg {
+            WasmMsg::Execute {
+                contract_addr: collection_addr.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft::<Extension, Extension> {
+                    recipient: SOMEBODY.to_string(),
+                    token_id: token_id.to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()
+        };
+        let msgs = vec![
+            send(600),
+            send(600),
+            WasmMsg::Execute {
+                contract_addr: "contract99".to_string(),
+                msg: to_binary("unused").unwrap(),
+                funds: vec![],
+            }
+            .into(),
+            transfer_nft(TOKEN_ID),
+            transfer_nft(TOKEN_ID),
+            transfer_nft("token0002"),
+        ];
+        let proposal_id = propose(&mut app, &dao_addr, OWNER, msgs);
+
+        let res: SimulationResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::SimulateProposal { proposal_id })
+            .unwrap();
+        let warnings: Vec<Vec<String>> = res.messages.into_iter().map(|m| m.warnings).collect();
+        assert_eq!(
+            warnings,
+            vec![
+                vec![],
+                vec!["insufficient ustars balance: 600 needed, 400 available".to_string()],
+                vec!["contract contract99 does not exist".to_string()],
+                vec![],
+                vec![format!(
+                    "NFT {} of {} already leaves the treasury in a previous message",
+                    TOKEN_ID, collection_addr
+                )],
+                vec![format!(
+                    "NFT token0002 of {} is not held by the treasury",
+                    collection_addr
+                )],
+            ]
+        );
+    }
+
+    #[test]
+    fn best_effort_execution_records_failures() {
+        let init_funds = coins(1000, "ustars");
+        let mut app = mock_app(&init_funds);
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
+
+        // the treasury has no BTC, so the second message fails
+        let send = |amount: Vec<Coin>| -> CosmosMsg {
+            BankMsg::Send {
+                to_address: SOMEBODY.to_string(),
+                amount,
+            }
+            .into()
+        };
+        let res = app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                dao_addr.clone(),
+                &ExecuteMsg::Propose {
+                    title: "Pay somebody".to_string(),
+                    description: "Whatever we can".to_string(),
+                    msgs: vec![
+                        send(coins(100, "ustars")),
+                        send(coins(1, "BTC")),
+                        send(coins(200, "ustars")),
+                    ],
+                    actions: vec![],
+                    metadata: None,
+                    latest: None,
+                    execution_mode: Some(ExecutionMode::BestEffort),
+                    proposer_vote: None,
+                },
+                &[],
+            )
+            .unwrap();
+        let proposal_id = proposal_id(&res);
+
+        let query = QueryMsg::ExecutionResults { proposal_id };
+        let res: ExecutionResultsResponse = app.wrap().query_wasm_smart(&dao_addr, &query).unwrap();
+        assert_eq!(res.mode, ExecutionMode::BestEffort);
+        assert_eq!(res.status, None);
+
+        execute_proposal(&mut app, &dao_addr, proposal_id);
+
+        // the messages that could succeed did
+        assert_eq!(
+            app.wrap().query_balance(SOMEBODY, "ustars").unwrap(),
+            coin(300, "ustars")
+        );
+        let res: ExecutionResultsResponse = app.wrap().query_wasm_smart(&dao_addr, &query).unwrap();
+        assert_eq!(res.status, Some(ExecutionStatus::ExecutionFailed));
+        assert_eq!(res.results.len(), 3);
+        assert_eq!(res.results[0], MessageResult::Success {});
+        assert!(matches!(res.results[1], MessageResult::Failure { .. }));
+        assert_eq!(res.results[2], MessageResult::Success {});
+
+        // the proposal is done with, and shows that a message failed
+        let prop: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::Proposal { proposal_id })
+            .unwrap();
+        assert_eq!(prop.status, Status::Executed);
+        assert_eq!(
+            prop.execution_status,
+            Some(ExecutionStatus::ExecutionFailed)
+        );
+
+        // failed executions can be listed
+        let list = |app: &App, execution_status: ExecutionStatus| -> Vec<u64> {
+            let res: ProposalListResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &dao_addr,
+                    &QueryMsg::ListProposalsFiltered {
+                        status: Some(Status::Executed),
+                        proposer: None,
+                        execution_status: Some(execution_status),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            res.proposals.iter().map(|p| p.id).collect()
+        };
+        assert_eq!(
+            list(&app, ExecutionStatus::ExecutionFailed),
+            vec![proposal_id]
+        );
+        assert!(list(&app, ExecutionStatus::Succeeded).is_empty());
+    }
+
+    #[test]
+    fn dao_proposes_stargaze_messages() {
+        let init_funds = coins(1000, "ustars");
+        let mut app = mock_app(&init_funds);
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
+
+        let msgs = vec![
+            create_fund_fairburn_pool_msg(coins(300, "ustars")),
+            create_fund_community_pool_msg(coins(200, "ustars")),
+            create_claim_for_msg(SOMEBODY.to_string(), ClaimAction::MintNFT),
+        ];
+        let proposal_id = propose(&mut app, &dao_addr, OWNER, msgs.clone());
+
+        // custom messages are stored and returned as proposed
+        let prop: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::Proposal { proposal_id })
+            .unwrap();
+        assert_eq!(prop.msgs, msgs);
+        let res: SimulationResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::SimulateProposal { proposal_id })
+            .unwrap();
+        assert!(res.messages.iter().all(|m| m.warnings.is_empty()));
+
+        execute_proposal(&mut app, &dao_addr, proposal_id);
+        assert_eq!(
+            app.wrap().query_balance("fairburn_pool", "ustars").unwrap(),
+            coin(300, "ustars")
+        );
+        assert_eq!(
+            app.wrap()
+                .query_balance("community_pool", "ustars")
+                .unwrap(),
+            coin(200, "ustars")
+        );
+
+        // funding a pool with more than the treasury holds is flagged
+        let msgs = vec![create_fund_fairburn_pool_msg(coins(1000, "ustars"))];
+        let proposal_id = propose(&mut app, &dao_addr, OWNER, msgs);
+        let res: SimulationResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::SimulateProposal { proposal_id })
+            .unwrap();
+        assert_eq!(
+            res.messages[0].warnings,
+            vec!["insufficient ustars balance: 1000 needed, 500 available".to_string()]
+        );
+    }
+
+    #[test]
+    fn typed_actions_execute_and_render() {
+        let init_funds = coins(1000, "ustars");
+        let mut app = mock_app(&init_funds);
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
+
+        let propose = |app: &mut App, actions: Vec<ProposalAction>| {
+            app.execute_contract(
+                Addr::unchecked(OWNER),
+                dao_addr.clone(),
+                &ExecuteMsg::Propose {
+                    title: "Housekeeping".to_string(),
+                    description: "Typed actions".to_string(),
+                    msgs: vec![],
+                    actions,
+                    metadata: None,
+                    latest: None,
+                    execution_mode: None,
+                    proposer_vote: None,
+                },
+                &[],
+            )
+        };
+
+        // actions are validated when proposed
+        let err = propose(
+            &mut app,
+            vec![ProposalAction::SendTokens {
+                recipient: SOMEBODY.to_string(),
+                denom: Denom::Native("ustars".to_string()),
+                amount: Uint128::zero(),
+            }],
+        )
+        .unwrap_err();
+        assert_eq!(ContractError::InvalidAction {}, err.downcast().unwrap());
+
+        let actions = vec![
+            ProposalAction::SendTokens {
+                recipient: SOMEBODY.to_string(),
+                denom: Denom::Native("ustars".to_string()),
+                amount: Uint128::new(100),
+            },
+            ProposalAction::InstantiateContract(ContractInstantiateMsg {
+                code_id: 3,
+                msg: to_binary(&Cw721InstantiateMsg {
+                    name: "dao collection".to_string(),
+                    symbol: "DAO NFT".to_string(),
+                    minter: dao_addr.to_string(),
+                })
+                .unwrap(),
+                admin: Some(Admin::Creator {}),
+                label: "collection".to_string(),
+            }),
+            ProposalAction::UpdateMetadata {
+                name: "name2".to_string(),
+                description: "description2".to_string(),
+                image: "image2".to_string(),
+            },
+            ProposalAction::UpdateConfig {
+                threshold: Threshold::AbsoluteCount { weight: 12 },
+                max_voting_period: voting_period,
+                min_voting_period: None,
+                grace_period: Some(Duration::Time(3600)),
+                executor: Some(crate::state::Executor::Member),
+            },
+        ];
+        let proposal_id = proposal_id(&propose(&mut app, actions.clone()).unwrap());
+
+        // actions are rendered in a human-readable form
+        let prop: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::Proposal { proposal_id })
+            .unwrap();
+        let rendered: Vec<_> = prop.actions.iter().map(|a| a.action.clone()).collect();
+        assert_eq!(rendered, actions);
+        let summaries: Vec<_> = prop.actions.iter().map(|a| a.summary.as_str()).collect();
+        assert_eq!(
+            summaries,
+            vec![
+                "Send 100ustars to somebody",
+                "Instantiate code 3 labeled \"collection\" with the DAO as admin",
+                "Update the DAO name to \"name2\", its description to \"description2\" and its image to image2",
+                "Update the DAO config to pass with 12 yes votes, vote for at most 2000000 seconds, \
+                 wait 3600 seconds before execution, let members execute",
+            ]
+        );
+
+        execute_proposal(&mut app, &dao_addr, proposal_id);
+
+        assert_eq!(
+            app.wrap().query_balance(SOMEBODY, "ustars").unwrap(),
+            coin(100, "ustars")
+        );
+        let res: ContractInfoResponse = app
+            .wrap()
+            .query_wasm_smart("contract4", &Cw721QueryMsg::ContractInfo {})
+            .unwrap();
+        assert_eq!(res.name, "dao collection");
+        let res: MetadataResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::Metadata {})
+            .unwrap();
+        assert_eq!("name2", res.name);
+        let res: ThresholdResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::Threshold {})
+            .unwrap();
+        assert_eq!(
+            res,
+            ThresholdResponse::AbsoluteCount {
+                weight: 12,
+                total_weight: 24
+            }
+        );
+    }
+
+    #[test]
+    fn proposal_metadata_is_stored_and_filtered() {
+        let mut app = mock_app(&[]);
+
+        let threshold = Threshold::AbsoluteCount { weight: 12 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, vec![], None);
+
+        let propose = |app: &mut App, metadata: Option<ProposalMetadata>| {
+            app.execute_contract(
+                Addr::unchecked(OWNER),
+                dao_addr.clone(),
+                &ExecuteMsg::Propose {
+                    title: "Title".to_string(),
+                    description: "Description".to_string(),
+                    msgs: vec![],
+                    actions: vec![],
+                    metadata,
+                    latest: None,
+                    execution_mode: None,
+                    proposer_vote: None,
+                },
+                &[],
+            )
+        };
+
+        // oversized metadata is rejected
+        let err = propose(
+            &mut app,
+            Some(ProposalMetadata {
+                category: ProposalCategory::Treasury,
+                discussion_url: Some("x".repeat(MAX_DISCUSSION_URL_LENGTH + 1)),
+                content_hash: None,
+            }),
+        )
+        .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidMetadata {
+                field: "discussion_url".to_string(),
+                max: MAX_DISCUSSION_URL_LENGTH,
+            },
+            err.downcast().unwrap()
+        );
+
+        let treasury = ProposalMetadata {
+            category: ProposalCategory::Treasury,
+            discussion_url: Some("https://forum.stargaze.zone/t/budget".to_string()),
+            content_hash: Some(
+                "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string(),
+            ),
+        };
+        propose(&mut app, Some(treasury.clone())).unwrap();
+        propose(
+            &mut app,
+            Some(ProposalMetadata {
+                category: ProposalCategory::Governance,
+                discussion_url: None,
+                content_hash: None,
+            }),
+        )
+        .unwrap();
+        propose(&mut app, None).unwrap();
+
+        let prop: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::Proposal { proposal_id: 1 })
+            .unwrap();
+        assert_eq!(prop.metadata, Some(treasury));
+
+        let list = |app: &App, category: Option<ProposalCategory>| -> Vec<u64> {
+            let res: ProposalListResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &dao_addr,
+                    &QueryMsg::ListProposals {
+                        start_after: None,
+                        limit: None,
+                        category,
+                    },
+                )
+                .unwrap();
+            res.proposals.iter().map(|p| p.id).collect()
+        };
+        assert_eq!(list(&app, None), vec![1, 2, 3]);
+        assert_eq!(list(&app, Some(ProposalCategory::Treasury)), vec![1]);
+        assert_eq!(list(&app, Some(ProposalCategory::Governance)), vec![2]);
+        assert!(list(&app, Some(ProposalCategory::Other)).is_empty());
+    }
+
+    #[test]
+    fn list_proposals_filtered_by_status_and_proposer() {
+        let mut app = mock_app(&[]);
+
+        let threshold = Threshold::AbsoluteCount { weight: 12 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, vec![], None);
+
+        let open_then_expired = propose(&mut app, &dao_addr, OWNER, vec![]);
+        let passed = propose(&mut app, &dao_addr, VOTER4, vec![]);
+        let executed = propose(&mut app, &dao_addr, VOTER4, vec![]);
+        execute_proposal(&mut app, &dao_addr, executed);
+        let expired_too = propose(&mut app, &dao_addr, VOTER1, vec![]);
+
+        app.update_block(expire(voting_period));
+        let open = propose(&mut app, &dao_addr, VOTER1, vec![]);
+
+        let list = |app: &App,
+                    status: Option<Status>,
+                    proposer: Option<&str>,
+                    start_after: Option<u64>,
+                    limit: Option<u32>|
+         -> Vec<u64> {
+            let res: ProposalListResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &dao_addr,
+                    &QueryMsg::ListProposalsFiltered {
+                        status,
+                        proposer: proposer.map(String::from),
+                        execution_status: None,
+                        start_after,
+                        limit,
+                    },
+                )
+                .unwrap();
+            res.proposals.iter().map(|p| p.id).collect()
+        };
+
+        // expired proposals are still stored as open but listed as rejected
+        assert_eq!(list(&app, Some(Status::Open), None, None, None), vec![open]);
+        assert_eq!(
+            list(&app, Some(Status::Rejected), None, None, None),
+            vec![open_then_expired, expired_too]
+        );
+        assert_eq!(
+            list(&app, Some(Status::Passed), None, None, None),
+            vec![passed]
+        );
+        assert_eq!(
+            list(&app, Some(Status::Executed), None, None, None),
+            vec![executed]
+        );
+
+        assert_eq!(
+            list(&app, None, Some(VOTER1), None, None),
+            vec![expired_too, open]
+        );
+        assert_eq!(
+            list(&app, Some(Status::Rejected), Some(OWNER), None, None),
+            vec![open_then_expired]
+        );
+        assert_eq!(
+            list(&app, Some(Status::Rejected), None, None, Some(1)),
+            vec![open_then_expired]
+        );
+        assert_eq!(
+            list(
+                &app,
+                Some(Status::Rejected),
+                None,
+                Some(open_then_expired),
+                None
+            ),
+            vec![expired_too]
+        );
+        assert_eq!(list(&app, None, None, None, None).len(), 5);
+    }
+
+    #[test]
+    fn list_votes_by_voter() {
+        let mut app = mock_app(&[]);
+
+        let threshold = Threshold::AbsoluteCount { weight: 20 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, vec![], None);
+
+        let proposed = propose(&mut app, &dao_addr, VOTER2, proposal_info().0);
+        let voted = propose(&mut app, &dao_addr, OWNER, proposal_info().0);
+        app.execute_contract(
+            Addr::unchecked(VOTER2),
+            dao_addr.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: voted,
+                vote: Vote::No,
+                rationale: Some("Too expensive".to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+        // VOTER2 does not vote on this one
+        propose(&mut app, &dao_addr, OWNER, proposal_info().0);
+
+        let list = |app: &App, start_after: Option<u64>, limit: Option<u32>| {
+            let res: VoteListResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &dao_addr,
+                    &QueryMsg::ListVotesByVoter {
+                        voter: VOTER2.to_string(),
+                        start_after,
+                        limit,
+                    },
+                )
+                .unwrap();
+            res.votes
+        };
+        let expected = vec![
+            VoteInfo {
+                proposal_id: proposed,
+                voter: VOTER2.to_string(),
+                vote: Vote::Yes,
+                weight: 2,
+                rationale: None,
+            },
+            VoteInfo {
+                proposal_id: voted,
+                voter: VOTER2.to_string(),
+                vote: Vote::No,
+                weight: 2,
+                rationale: Some("Too expensive".to_string()),
+            },
+        ];
+        assert_eq!(list(&app, None, None), expected);
+        assert_eq!(list(&app, None, Some(1)), expected[..1]);
+        assert_eq!(list(&app, Some(proposed), None), expected[1..]);
+        assert!(list(&app, Some(voted), None).is_empty());
+    }
+
+    #[test]
+    fn participation_stats_track_turnout() {
+        let mut app = mock_app(&[]);
+
+        let threshold = Threshold::AbsoluteCount { weight: 20 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, vec![], None);
+
+        // 12 of 24
+        let proposal_id1 = propose(&mut app, &dao_addr, VOTER4, proposal_info().0);
+        // 1 + 5 of 24
+        let proposal_id2 = propose(&mut app, &dao_addr, OWNER, proposal_info().0);
+        vote(&mut app, &dao_addr, VOTER5, proposal_id2, Vote::No);
+
+        let res: ParticipationStatsResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::ParticipationStats {})
+            .unwrap();
+        assert_eq!(
+            res.turnouts,
+            vec![
+                ProposalTurnout {
+                    proposal_id: proposal_id2,
+                    turnout: Decimal::percent(25),
+                },
+                ProposalTurnout {
+                    proposal_id: proposal_id1,
+                    turnout: Decimal::percent(50),
+                },
+            ]
+        );
+        assert_eq!(res.average_turnout, Decimal::permille(375));
+
+        // closing keeps the final turnout
+        app.update_block(expire(voting_period));
+        app.execute_contract(
+            Addr::unchecked(SOMEBODY),
+            dao_addr.clone(),
+            &ExecuteMsg::Close {
+                proposal_id: proposal_id1,
+            },
+            &[],
+        )
+        .unwrap();
+        let res: ParticipationStatsResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::ParticipationStats {})
+            .unwrap();
+        assert_eq!(res.average_turnout, Decimal::permille(375));
+
+        let proposals_voted = |app: &App, address: &str| -> u64 {
+            let res: MemberParticipationResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &dao_addr,
+                    &QueryMsg::MemberParticipation {
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap();
+            res.proposals_voted
+        };
+        assert_eq!(proposals_voted(&app, VOTER4), 1);
+        assert_eq!(proposals_voted(&app, OWNER), 1);
+        assert_eq!(proposals_voted(&app, VOTER5), 1);
+        assert_eq!(proposals_voted(&app, VOTER1), 0);
+    }
+
+    #[test]
+    fn proposer_chooses_initial_vote() {
+        let mut app = mock_app(&[]);
+
+        let threshold = Threshold::AbsoluteCount { weight: 12 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, vec![], None);
+
+        let propose = |app: &mut App, proposer_vote: ProposerVote| -> u64 {
+            let (msgs, title, description) = proposal_info();
+            let res = app
+                .execute_contract(
+                    Addr::unchecked(VOTER4),
+                    dao_addr.clone(),
+                    &ExecuteMsg::Propose {
+                        title,
+                        description,
+                        msgs,
+                        actions: vec![],
+                        metadata: None,
+                        latest: None,
+                        execution_mode: None,
+                        proposer_vote: Some(proposer_vote),
+                    },
+                    &[],
+                )
+                .unwrap();
+            proposal_id(&res)
+        };
+        let query_vote = |app: &App, proposal_id: u64| -> Option<VoteInfo> {
+            let res: VoteResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &dao_addr,
+                    &QueryMsg::Vote {
+                        proposal_id,
+                        voter: VOTER4.to_string(),
+                    },
+                )
+                .unwrap();
+            res.vote
+        };
+
+        // without voting the proposal stays open until the proposer votes
+        let proposal_id = propose(&mut app, ProposerVote::Skip {});
+        assert_eq!(status(&app, &dao_addr, proposal_id), Status::Open);
+        assert_eq!(query_vote(&app, proposal_id), None);
+        let res = vote(&mut app, &dao_addr, VOTER4, proposal_id, Vote::Yes);
+        assert_eq!(
+            res.custom_attrs(1),
+            [
+                ("action", "vote"),
+                ("sender", VOTER4),
+                ("proposal_id", proposal_id.to_string().as_str()),
+                ("status", "Passed"),
+            ],
+        );
+
+        // the initial vote can be any option
+        let proposal_id = propose(&mut app, ProposerVote::Cast(Vote::No));
+        let ballot = query_vote(&app, proposal_id).unwrap();
+        assert_eq!((ballot.vote, ballot.weight), (Vote::No, 12));
+        let err = app
+            .execute_contract(
+                Addr::unchecked(VOTER4),
+                dao_addr.clone(),
+                &ExecuteMsg::Vote {
+                    proposal_id,
+                    vote: Vote::Yes,
+                    rationale: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::AlreadyVoted {}, err.downcast().unwrap());
     }
 
     #[test]
@@ -1634,7 +2947,11 @@ mod tests {
                     title: "proposal_title".to_string(),
                     description: "proposal_description".to_string(),
                     msgs: vec![wasm_msg.into()],
+                    actions: vec![],
+                    metadata: None,
                     latest: None,
+                    execution_mode: None,
+                    proposer_vote: None,
                 },
                 &[],
             )
@@ -1648,6 +2965,7 @@ mod tests {
             &ExecuteMsg::Vote {
                 proposal_id,
                 vote: Vote::Yes,
+                rationale: None,
             },
             &[],
         )
@@ -0,0 +1,235 @@
+#[cfg(test)]
+mod tests {
+    use std::vec;
+
+    use crate::integration_helpers::*;
+    use crate::{
+        msg::{
+            AllowanceListResponse, ExecuteMsg, Group, InstantiateMsg, MetadataResponse,
+            ParentResponse, QueryMsg, SubDaoListResponse,
+        },
+        ContractError,
+    };
+    use cosmwasm_std::{coin, coins, to_binary, Addr, Uint128, WasmMsg};
+    use cw20::Denom;
+    use cw_multi_test::Executor;
+    use cw_utils::{Duration, Threshold};
+    use sg_daos::ContractInstantiateMsg;
+    use sg_std::CosmosMsg;
+
+    #[test]
+    fn sub_dao_lifecycle() {
+        let mut app = mock_app(&coins(1500, "ustars"));
+
+        let threshold = Threshold::AbsoluteCount { weight: 12 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(
+            &mut app,
+            threshold.clone(),
+            voting_period,
+            coins(1000, "ustars"),
+            None,
+        );
+        let dao_id = app.store_code(contract_nft_dao());
+
+        // the working group is governed by the same members
+        let create = ExecuteMsg::CreateSubDao(ContractInstantiateMsg {
+            code_id: dao_id,
+            msg: to_binary(&InstantiateMsg {
+                name: "working group".to_string(),
+                description: "description".to_string(),
+                image: "image".to_string(),
+                group: Group::Cw4Address(SG_NFT_GROUP_CONTRACT.to_string()),
+                threshold,
+                max_voting_period: voting_period,
+                min_voting_period: None,
+                grace_period: None,
+                executor: None,
+                parent: None,
+                registry: None,
+            })
+            .unwrap(),
+            admin: None,
+            label: "working group".to_string(),
+        });
+
+        // only via proposal
+        let err = app
+            .execute_contract(Addr::unchecked(OWNER), dao_addr.clone(), &create, &[])
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        propose_pass_execute(&mut app, dao_addr.clone(), dao_msg(&dao_addr, &create));
+
+        let list_sub_daos = |app: &App| -> SubDaoListResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    &dao_addr,
+                    &QueryMsg::ListSubDaos {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap()
+        };
+        let sub_daos = list_sub_daos(&app).sub_daos;
+        assert_eq!(sub_daos.len(), 1);
+        let sub_dao = sub_daos[0].addr.clone();
+        assert_eq!(sub_daos[0].budgets, vec![]);
+
+        // the parent administers the sub-DAO
+        let info = app.wrap().query_wasm_contract_info(&sub_dao).unwrap();
+        assert_eq!(info.admin, Some(dao_addr.to_string()));
+        let parent: ParentResponse = app
+            .wrap()
+            .query_wasm_smart(&sub_dao, &QueryMsg::Parent {})
+            .unwrap();
+        assert_eq!(
+            parent,
+            ParentResponse {
+                parent: Some(dao_addr.clone()),
+                dissolved: false,
+            }
+        );
+
+        // the budget of the sub-DAO is an allowance
+        let ustars = Denom::Native("ustars".to_string());
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(
+                &dao_addr,
+                &ExecuteMsg::SetAllowance {
+                    grantee: sub_dao.to_string(),
+                    denom: ustars.clone(),
+                    cap: Uint128::new(100),
+                    period: Duration::Time(1000),
+                },
+            ),
+        );
+        let budgets = list_sub_daos(&app).sub_daos[0].budgets.clone();
+        assert_eq!(budgets.len(), 1);
+        assert_eq!(budgets[0].remaining, Uint128::new(100));
+
+        // the sub-DAO spends its budget through its own proposals
+        propose_pass_execute(
+            &mut app,
+            sub_dao.clone(),
+            dao_msg(
+                &dao_addr,
+                &ExecuteMsg::Spend {
+                    to: SOMEBODY.to_string(),
+                    denom: ustars,
+                    amount: Uint128::new(60),
+                },
+            ),
+        );
+        let balance = |app: &App, addr: &str| app.wrap().query_balance(addr, "ustars").unwrap();
+        assert_eq!(balance(&app, SOMEBODY), coin(60, "ustars"));
+        assert_eq!(
+            list_sub_daos(&app).sub_daos[0].budgets[0].remaining,
+            Uint128::new(40)
+        );
+
+        // only the parent can act as the sub-DAO
+        let update_metadata = ExecuteMsg::UpdateMetadata {
+            name: "ops".to_string(),
+            description: "description".to_string(),
+            image: "image".to_string(),
+        };
+        let parent_execute = ExecuteMsg::ParentExecute {
+            msgs: vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: sub_dao.to_string(),
+                msg: to_binary(&update_metadata).unwrap(),
+                funds: vec![],
+            })],
+        };
+        let err = app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                sub_dao.clone(),
+                &parent_execute,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        let msgs = match parent_execute {
+            ExecuteMsg::ParentExecute { msgs } => msgs,
+            _ => unreachable!(),
+        };
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(
+                &dao_addr,
+                &ExecuteMsg::OverrideSubDao {
+                    addr: sub_dao.to_string(),
+                    msgs,
+                },
+            ),
+        );
+        let metadata: MetadataResponse = app
+            .wrap()
+            .query_wasm_smart(&sub_dao, &QueryMsg::Metadata {})
+            .unwrap();
+        assert_eq!(metadata.name, "ops");
+
+        // dissolving returns the treasury to the parent, leaving broken tokens behind
+        let broken = instantiate_broken_cw20(&mut app);
+        send_broken_cw20(&mut app, &broken, &sub_dao);
+        app.send_tokens(
+            Addr::unchecked(OWNER),
+            sub_dao.clone(),
+            &coins(200, "ustars"),
+        )
+        .unwrap();
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(
+                &dao_addr,
+                &ExecuteMsg::DissolveSubDao {
+                    addr: sub_dao.to_string(),
+                },
+            ),
+        );
+        assert_eq!(balance(&app, sub_dao.as_str()), coin(0, "ustars"));
+        assert_eq!(balance(&app, dao_addr.as_str()), coin(1140, "ustars"));
+        assert_eq!(list_sub_daos(&app).sub_daos, vec![]);
+        let allowances: AllowanceListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::ListAllowances {
+                    grantee: sub_dao.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(allowances.allowances, vec![]);
+
+        let parent: ParentResponse = app
+            .wrap()
+            .query_wasm_smart(&sub_dao, &QueryMsg::Parent {})
+            .unwrap();
+        assert!(parent.dissolved);
+
+        // nothing can be proposed, executed, spent or claimed anymore
+        let spend = ExecuteMsg::Spend {
+            to: SOMEBODY.to_string(),
+            denom: Denom::Native("ustars".to_string()),
+            amount: Uint128::new(1),
+        };
+        for msg in [
+            pay_somebody_proposal(),
+            ExecuteMsg::Execute { proposal_id: 1 },
+            spend,
+            ExecuteMsg::ClaimStream { id: 1 },
+        ] {
+            let err = app
+                .execute_contract(Addr::unchecked(OWNER), sub_dao.clone(), &msg, &[])
+                .unwrap_err();
+            assert_eq!(ContractError::Dissolved {}, err.downcast().unwrap());
+        }
+    }
+}
@@ -0,0 +1,835 @@
+#[cfg(test)]
+mod tests {
+    use std::vec;
+
+    use crate::integration_helpers::*;
+    use crate::{
+        msg::{
+            AllowanceListResponse, AllowanceResponse, CollectionListResponse, Cw20Balance,
+            ExecuteMsg, GroupResponse, NftListResponse, QueryMsg, RevenueSplitResponse,
+            StreamListResponse, StreamResponse, TreasuryBalancesResponse,
+        },
+        state::RevenueSplit,
+        ContractError,
+    };
+    use cosmwasm_std::{
+        coin, coins, to_binary, Addr, BankMsg, Binary, Decimal, Empty, Fraction, MessageInfo,
+        Response, StdError, StdResult, Uint128, WasmMsg,
+    };
+    use cw20::{Cw20ExecuteMsg, Denom};
+    use cw3::Vote;
+    use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+    use cw_multi_test::{Contract, ContractWrapper, Executor};
+    use cw_utils::{Duration, Expiration, Threshold};
+    use sg_daos::swap::{Asset, AssetInfo, PairExecuteMsg};
+    use sg_std::StargazeMsgWrapper;
+
+    /// Pair swapping ustars for uusdc at 2:1, checking slippage like Terraswap does
+    pub fn contract_mock_pair() -> Box<dyn Contract<StargazeMsgWrapper>> {
+        let contract = ContractWrapper::new_with_empty(
+            |_deps, _env, info: MessageInfo, msg: PairExecuteMsg| -> StdResult<Response> {
+                let PairExecuteMsg::Swap {
+                    offer_asset,
+                    belief_price,
+                    max_spread,
+                    to,
+                } = msg;
+                let return_amount = offer_asset.amount / Uint128::new(2);
+                if let (Some(price), Some(spread)) = (belief_price, max_spread) {
+                    let expected = offer_asset.amount * price.inv().unwrap();
+                    if return_amount < expected * (Decimal::one() - spread) {
+                        return Err(StdError::generic_err("Operation exceeds max spread limit"));
+                    }
+                }
+                Ok(Response::new().add_message(BankMsg::Send {
+                    to_address: to.unwrap_or_else(|| info.sender.to_string()),
+                    amount: coins(return_amount.u128(), "uusdc"),
+                }))
+            },
+            |_deps, _env, _info, _msg: Empty| -> StdResult<Response> { Ok(Response::new()) },
+            |_deps, _env, _msg: Empty| -> StdResult<Binary> {
+                Err(StdError::generic_err("no queries"))
+            },
+        );
+        Box::new(contract)
+    }
+
+    #[test]
+    fn treasury_tracks_received_nfts() {
+        let mut app = mock_app(&[]);
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, vec![], None);
+
+        let collection_addr = setup_test_collection(&mut app);
+        app.execute_contract(
+            Addr::unchecked(MINTER),
+            collection_addr.clone(),
+            &Cw721ExecuteMsg::Mint::<Extension, Extension>(MintMsg::<Extension> {
+                token_id: "token0002".to_string(),
+                owner: OWNER.to_string(),
+                token_uri: None,
+                extension: None,
+            }),
+            &[],
+        )
+        .unwrap();
+        let collection_addr2 = instantiate_collection(&mut app);
+
+        // send NFTs to the DAO
+        for token_id in [TOKEN_ID, "token0002"] {
+            let res = app
+                .execute_contract(
+                    Addr::unchecked(OWNER),
+                    collection_addr.clone(),
+                    &Cw721ExecuteMsg::SendNft::<Extension, Extension> {
+                        contract: dao_addr.to_string(),
+                        token_id: token_id.to_string(),
+                        msg: to_binary("unused").unwrap(),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                res.custom_attrs(3),
+                [
+                    ("action", "receive_nft"),
+                    ("collection", collection_addr.as_str()),
+                    ("from", OWNER),
+                    ("token_id", token_id),
+                ],
+            );
+        }
+
+        let list_nfts = |app: &App, collection: &Addr| -> Vec<String> {
+            let res: NftListResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &dao_addr,
+                    &QueryMsg::ListNfts {
+                        collection: collection.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            res.tokens
+        };
+        let list_collections = |app: &App| -> Vec<Addr> {
+            let res: CollectionListResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &dao_addr,
+                    &QueryMsg::ListCollections {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            res.collections
+        };
+
+        assert_eq!(
+            list_nfts(&app, &collection_addr),
+            vec![TOKEN_ID.to_string(), "token0002".to_string()]
+        );
+        assert!(list_nfts(&app, &collection_addr2).is_empty());
+        assert_eq!(list_collections(&app), vec![collection_addr.clone()]);
+
+        // NFTs given away by a proposal leave the inventory
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            WasmMsg::Execute {
+                contract_addr: collection_addr.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft::<Extension, Extension> {
+                    recipient: SOMEBODY.into(),
+                    token_id: TOKEN_ID.into(),
+                })
+                .unwrap(),
+                funds: vec![],
+            },
+        );
+        assert_eq!(
+            list_nfts(&app, &collection_addr),
+            vec!["token0002".to_string()]
+        );
+
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            WasmMsg::Execute {
+                contract_addr: collection_addr.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::Burn::<Extension, Extension> {
+                    token_id: "token0002".into(),
+                })
+                .unwrap(),
+                funds: vec![],
+            },
+        );
+        assert!(list_nfts(&app, &collection_addr).is_empty());
+        assert!(list_collections(&app).is_empty());
+    }
+
+    #[test]
+    fn treasury_reports_balances() {
+        let init_funds = coins(10, "ustars");
+        let mut app = mock_app(&init_funds);
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
+
+        let token1 = instantiate_cw20(&mut app, "ONE", 1000);
+        let token2 = instantiate_cw20(&mut app, "TWO", 2000);
+
+        // sending a token registers it
+        let res = app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                token1.clone(),
+                &Cw20ExecuteMsg::Send {
+                    contract: dao_addr.to_string(),
+                    amount: Uint128::new(100),
+                    msg: to_binary("unused").unwrap(),
+                },
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            res.custom_attrs(3),
+            [
+                ("action", "receive"),
+                ("token", token1.as_str()),
+                ("from", OWNER),
+                ("amount", "100"),
+            ],
+        );
+
+        // tokens sent with a plain transfer are unknown until registered
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            token2.clone(),
+            &Cw20ExecuteMsg::Transfer {
+                recipient: dao_addr.to_string(),
+                amount: Uint128::new(200),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let balances = |app: &App, start_after: Option<&Addr>| -> TreasuryBalancesResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    &dao_addr,
+                    &QueryMsg::TreasuryBalances {
+                        start_after: start_after.map(Addr::to_string),
+                        limit: Some(1),
+                    },
+                )
+                .unwrap()
+        };
+        assert_eq!(
+            balances(&app, None),
+            TreasuryBalancesResponse {
+                native: coins(10, "ustars"),
+                cw20: vec![Cw20Balance {
+                    address: token1.clone(),
+                    amount: Uint128::new(100),
+                }],
+                unavailable: vec![],
+            }
+        );
+        assert!(balances(&app, Some(&token1)).cw20.is_empty());
+
+        // only the DAO itself can update the token list
+        let update = ExecuteMsg::UpdateTokenList {
+            to_add: vec![token2.to_string()],
+            to_remove: vec![],
+        };
+        let err = app
+            .execute_contract(Addr::unchecked(OWNER), dao_addr.clone(), &update, &[])
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        propose_pass_execute(&mut app, dao_addr.clone(), dao_msg(&dao_addr, &update));
+        let page = balances(&app, Some(&token1));
+        assert_eq!(page.native, coins(10, "ustars"));
+        assert_eq!(
+            page.cw20,
+            vec![Cw20Balance {
+                address: token2.clone(),
+                amount: Uint128::new(200),
+            }]
+        );
+
+        // a token failing its balance query is reported instead of breaking the query
+        let broken = instantiate_broken_cw20(&mut app);
+        send_broken_cw20(&mut app, &broken, &dao_addr);
+        let page = balances(&app, Some(&token2));
+        assert_eq!(page.cw20, vec![]);
+        assert_eq!(page.unavailable, vec![broken]);
+    }
+
+    #[test]
+    fn streams_vest_over_time() {
+        let init_funds = coins(1000, "ustars");
+        let mut app = mock_app(&init_funds);
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
+
+        // 10 ustars every 100 seconds for 1000 seconds, nothing before 300 seconds
+        let start = app.block_info().time;
+        let create = ExecuteMsg::CreateStream {
+            recipient: SOMEBODY.to_string(),
+            denom: Denom::Native("ustars".to_string()),
+            amount_per_interval: Uint128::new(10),
+            interval: 100,
+            start,
+            end: start.plus_seconds(1000),
+            cliff: Some(start.plus_seconds(300)),
+        };
+
+        // streams are created by proposals only
+        let err = app
+            .execute_contract(Addr::unchecked(OWNER), dao_addr.clone(), &create, &[])
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        propose_pass_execute(&mut app, dao_addr.clone(), dao_msg(&dao_addr, &create));
+
+        let claim = ExecuteMsg::ClaimStream { id: 1 };
+        let err = app
+            .execute_contract(Addr::unchecked(SOMEBODY), dao_addr.clone(), &claim, &[])
+            .unwrap_err();
+        assert_eq!(ContractError::NothingToClaim {}, err.downcast().unwrap());
+
+        // only the recipient can claim
+        app.update_block(|block| block.time = start.plus_seconds(350));
+        let err = app
+            .execute_contract(Addr::unchecked(OWNER), dao_addr.clone(), &claim, &[])
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        let res = app
+            .execute_contract(Addr::unchecked(SOMEBODY), dao_addr.clone(), &claim, &[])
+            .unwrap();
+        assert_eq!(
+            res.custom_attrs(1),
+            [
+                ("action", "claim_stream"),
+                ("sender", SOMEBODY),
+                ("stream_id", "1"),
+                ("amount", "30"),
+            ],
+        );
+
+        app.update_block(|block| block.time = start.plus_seconds(550));
+        let stream: StreamResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::Stream { id: 1 })
+            .unwrap();
+        assert_eq!(stream.claimable, Uint128::new(20));
+        assert_eq!(stream.stream.claimed, Uint128::new(30));
+
+        // cancelling pays out what has vested and keeps the rest in the treasury
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(&dao_addr, &ExecuteMsg::CancelStream { id: 1 }),
+        );
+        let streams: StreamListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::ListStreams {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert!(streams.streams.is_empty());
+
+        let balance = |app: &App, addr: &str| app.wrap().query_balance(addr, "ustars").unwrap();
+        assert_eq!(balance(&app, SOMEBODY), coin(50, "ustars"));
+        assert_eq!(balance(&app, dao_addr.as_str()), coin(950, "ustars"));
+    }
+
+    #[test]
+    fn allowances_cap_spending_per_period() {
+        let init_funds = coins(1000, "ustars");
+        let mut app = mock_app(&init_funds);
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
+
+        let ustars = Denom::Native("ustars".to_string());
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(
+                &dao_addr,
+                &ExecuteMsg::SetAllowance {
+                    grantee: VOTER1.to_string(),
+                    denom: ustars.clone(),
+                    cap: Uint128::new(100),
+                    period: Duration::Time(1000),
+                },
+            ),
+        );
+
+        let spend = |amount: u128| ExecuteMsg::Spend {
+            to: SOMEBODY.to_string(),
+            denom: ustars.clone(),
+            amount: Uint128::new(amount),
+        };
+
+        // only grantees can spend
+        let err = app
+            .execute_contract(Addr::unchecked(VOTER2), dao_addr.clone(), &spend(10), &[])
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        let res = app
+            .execute_contract(Addr::unchecked(VOTER1), dao_addr.clone(), &spend(60), &[])
+            .unwrap();
+        assert_eq!(
+            res.custom_attrs(1),
+            [
+                ("action", "spend"),
+                ("sender", VOTER1),
+                ("to", SOMEBODY),
+                ("denom", "native:ustars"),
+                ("amount", "60"),
+            ],
+        );
+
+        let err = app
+            .execute_contract(Addr::unchecked(VOTER1), dao_addr.clone(), &spend(50), &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::AllowanceExceeded {
+                remaining: Uint128::new(40)
+            },
+            err.downcast().unwrap()
+        );
+
+        let query = QueryMsg::Allowance {
+            grantee: VOTER1.to_string(),
+            denom: ustars.clone(),
+        };
+        let allowance: AllowanceResponse = app.wrap().query_wasm_smart(&dao_addr, &query).unwrap();
+        assert_eq!(allowance.remaining, Uint128::new(40));
+        let resets = match allowance.resets {
+            Expiration::AtTime(resets) => resets,
+            _ => panic!("expected a time based reset"),
+        };
+
+        // the full cap is available again in the next period
+        app.update_block(|block| block.time = resets);
+        let allowance: AllowanceResponse = app.wrap().query_wasm_smart(&dao_addr, &query).unwrap();
+        assert_eq!(allowance.remaining, Uint128::new(100));
+        assert_eq!(
+            allowance.resets,
+            Expiration::AtTime(resets.plus_seconds(1000))
+        );
+        app.execute_contract(Addr::unchecked(VOTER1), dao_addr.clone(), &spend(100), &[])
+            .unwrap();
+        assert_eq!(
+            app.wrap().query_balance(SOMEBODY, "ustars").unwrap(),
+            coin(160, "ustars")
+        );
+
+        // lowering the cap below what was spent leaves nothing for the period
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(
+                &dao_addr,
+                &ExecuteMsg::SetAllowance {
+                    grantee: VOTER1.to_string(),
+                    denom: ustars.clone(),
+                    cap: Uint128::new(50),
+                    period: Duration::Time(1000),
+                },
+            ),
+        );
+        let allowance: AllowanceResponse = app.wrap().query_wasm_smart(&dao_addr, &query).unwrap();
+        assert_eq!(allowance.cap, Uint128::new(50));
+        assert_eq!(allowance.remaining, Uint128::zero());
+        let err = app
+            .execute_contract(Addr::unchecked(VOTER1), dao_addr.clone(), &spend(1), &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::AllowanceExceeded {
+                remaining: Uint128::zero()
+            },
+            err.downcast().unwrap()
+        );
+        let allowances: AllowanceListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::ListAllowances {
+                    grantee: VOTER1.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(allowances.allowances[0].remaining, Uint128::zero());
+
+        // revoked allowances cannot be spent
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(
+                &dao_addr,
+                &ExecuteMsg::RevokeAllowance {
+                    grantee: VOTER1.to_string(),
+                    denom: ustars.clone(),
+                },
+            ),
+        );
+        app.update_block(|block| block.time = resets.plus_seconds(1000));
+        let err = app
+            .execute_contract(Addr::unchecked(VOTER1), dao_addr.clone(), &spend(10), &[])
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+        let allowances: AllowanceListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &dao_addr,
+                &QueryMsg::ListAllowances {
+                    grantee: VOTER1.to_string(),
+                },
+            )
+            .unwrap();
+        assert!(allowances.allowances.is_empty());
+    }
+
+    #[test]
+    fn treasury_swaps_through_pair() {
+        let mut app = mock_app(&[coin(1000, "ustars"), coin(1000, "uusdc")]);
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let init_funds = coins(1000, "ustars");
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
+
+        let pair_id = app.store_code(contract_mock_pair());
+        let pair_addr = app
+            .instantiate_contract(
+                pair_id,
+                Addr::unchecked(OWNER),
+                &Empty {},
+                &[],
+                "pair",
+                None,
+            )
+            .unwrap();
+        app.send_tokens(
+            Addr::unchecked(OWNER),
+            pair_addr.clone(),
+            &coins(1000, "uusdc"),
+        )
+        .unwrap();
+
+        let swap = |min_receive: u128| ExecuteMsg::Swap {
+            pair: pair_addr.to_string(),
+            offer: Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ustars".to_string(),
+                },
+                amount: Uint128::new(100),
+            },
+            min_receive: Uint128::new(min_receive),
+        };
+        // swaps are made by proposals only
+        let err = app
+            .execute_contract(Addr::unchecked(OWNER), dao_addr.clone(), &swap(50), &[])
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        propose_pass_execute(&mut app, dao_addr.clone(), dao_msg(&dao_addr, &swap(50)));
+        let balances = app.wrap().query_all_balances(&dao_addr).unwrap();
+        assert_eq!(balances, vec![coin(900, "ustars"), coin(50, "uusdc")]);
+
+        // a swap returning less than the minimum fails the execution
+        let msgs = vec![dao_msg(&dao_addr, &swap(60)).into()];
+        let proposal_id = propose(&mut app, &dao_addr, OWNER, msgs);
+        vote(&mut app, &dao_addr, VOTER4, proposal_id, Vote::Yes);
+        app.execute_contract(
+            Addr::unchecked(SOMEBODY),
+            dao_addr.clone(),
+            &ExecuteMsg::Execute { proposal_id },
+            &[],
+        )
+        .unwrap_err();
+        let balances = app.wrap().query_all_balances(&dao_addr).unwrap();
+        assert_eq!(balances, vec![coin(900, "ustars"), coin(50, "uusdc")]);
+    }
+
+    #[test]
+    fn revenue_split_distributes_to_group() {
+        let mut app = mock_app(&coins(1480, "ustars"));
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let init_funds = coins(1000, "ustars");
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
+
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(
+                &dao_addr,
+                &ExecuteMsg::UpdateRevenueSplit {
+                    split: Some(RevenueSplit {
+                        group_share: Decimal::percent(50),
+                        denoms: vec!["ustars".to_string()],
+                    }),
+                },
+            ),
+        );
+
+        // funds held before the split was set are not distributed
+        let distribute = ExecuteMsg::Distribute {};
+        let err = app
+            .execute_contract(
+                Addr::unchecked(SOMEBODY),
+                dao_addr.clone(),
+                &distribute,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NothingToDistribute {},
+            err.downcast().unwrap()
+        );
+
+        // mint proceeds come in
+        app.send_tokens(
+            Addr::unchecked(OWNER),
+            dao_addr.clone(),
+            &coins(480, "ustars"),
+        )
+        .unwrap();
+        let res: RevenueSplitResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::RevenueSplit {})
+            .unwrap();
+        assert_eq!(res.pending, coins(480, "ustars"));
+
+        // half of it is distributed to the members by weight
+        app.execute_contract(
+            Addr::unchecked(SOMEBODY),
+            dao_addr.clone(),
+            &distribute,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            app.wrap().query_balance(VOTER4, "ustars").unwrap(),
+            coin(120, "ustars")
+        );
+        assert_eq!(
+            app.wrap().query_balance(&dao_addr, "ustars").unwrap(),
+            coin(1240, "ustars")
+        );
+
+        let res: RevenueSplitResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::RevenueSplit {})
+            .unwrap();
+        assert!(res.pending.is_empty());
+        let err = app
+            .execute_contract(
+                Addr::unchecked(SOMEBODY),
+                dao_addr.clone(),
+                &distribute,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NothingToDistribute {},
+            err.downcast().unwrap()
+        );
+
+        // funds paid out of the treasury do not hide revenue received afterwards
+        let msgs = vec![BankMsg::Send {
+            to_address: SOMEBODY.to_string(),
+            amount: coins(400, "ustars"),
+        }
+        .into()];
+        let proposal_id = propose(&mut app, &dao_addr, OWNER, msgs);
+        execute_proposal(&mut app, &dao_addr, proposal_id);
+        // VOTER4 pays back 100 of the 120 it received
+        app.send_tokens(
+            Addr::unchecked(VOTER4),
+            dao_addr.clone(),
+            &coins(100, "ustars"),
+        )
+        .unwrap();
+        let res: RevenueSplitResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::RevenueSplit {})
+            .unwrap();
+        assert_eq!(res.pending, coins(100, "ustars"));
+        app.execute_contract(Addr::unchecked(SOMEBODY), dao_addr, &distribute, &[])
+            .unwrap();
+        assert_eq!(
+            app.wrap().query_balance(VOTER4, "ustars").unwrap(),
+            coin(45, "ustars")
+        );
+    }
+
+    #[test]
+    fn revenue_split_ignores_swaps_and_auction_sales() {
+        let mut app = mock_app(&[coin(1000, "ustars"), coin(1000, "uusdc")]);
+
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let voting_period = Duration::Time(2000000);
+        let init_funds = coins(500, "ustars");
+        let dao_addr = setup_test_case(&mut app, threshold, voting_period, init_funds, None);
+        app.send_tokens(
+            Addr::unchecked(OWNER),
+            Addr::unchecked(VOTER1),
+            &coins(100, "ustars"),
+        )
+        .unwrap();
+
+        let pair_id = app.store_code(contract_mock_pair());
+        let pair_addr = app
+            .instantiate_contract(
+                pair_id,
+                Addr::unchecked(OWNER),
+                &Empty {},
+                &[],
+                "pair",
+                None,
+            )
+            .unwrap();
+        app.send_tokens(
+            Addr::unchecked(OWNER),
+            pair_addr.clone(),
+            &coins(1000, "uusdc"),
+        )
+        .unwrap();
+        let collection_addr = setup_test_collection(&mut app);
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            collection_addr.clone(),
+            &Cw721ExecuteMsg::SendNft::<Extension, Extension> {
+                contract: dao_addr.to_string(),
+                token_id: TOKEN_ID.to_string(),
+                msg: to_binary("unused").unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(
+                &dao_addr,
+                &ExecuteMsg::UpdateRevenueSplit {
+                    split: Some(RevenueSplit {
+                        group_share: Decimal::percent(50),
+                        denoms: vec!["ustars".to_string(), "uusdc".to_string()],
+                    }),
+                },
+            ),
+        );
+
+        // swap proceeds and auction sales are not revenue
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(
+                &dao_addr,
+                &ExecuteMsg::Swap {
+                    pair: pair_addr.to_string(),
+                    offer: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "ustars".to_string(),
+                        },
+                        amount: Uint128::new(100),
+                    },
+                    min_receive: Uint128::new(50),
+                },
+            ),
+        );
+        propose_pass_execute(
+            &mut app,
+            dao_addr.clone(),
+            dao_msg(
+                &dao_addr,
+                &ExecuteMsg::StartAuction {
+                    collection: collection_addr.to_string(),
+                    token_id: TOKEN_ID.to_string(),
+                    denom: "ustars".to_string(),
+                    reserve_price: Uint128::new(100),
+                    duration: Duration::Time(1000),
+                },
+            ),
+        );
+        app.execute_contract(
+            Addr::unchecked(VOTER1),
+            dao_addr.clone(),
+            &ExecuteMsg::Bid { auction_id: 1 },
+            &coins(100, "ustars"),
+        )
+        .unwrap();
+        app.update_block(expire(Duration::Time(1000)));
+        app.execute_contract(
+            Addr::unchecked(SOMEBODY),
+            dao_addr.clone(),
+            &ExecuteMsg::SettleAuction { auction_id: 1 },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            app.wrap().query_all_balances(&dao_addr).unwrap(),
+            vec![coin(500, "ustars"), coin(50, "uusdc")]
+        );
+        let res: RevenueSplitResponse = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::RevenueSplit {})
+            .unwrap();
+        assert!(res.pending.is_empty());
+
+        // only the group share of revenue is distributed,
+        // funds the group already holds stay there
+        let GroupResponse { group } = app
+            .wrap()
+            .query_wasm_smart(&dao_addr, &QueryMsg::Group {})
+            .unwrap();
+        app.send_tokens(Addr::unchecked(OWNER), group.addr(), &coins(24, "ustars"))
+            .unwrap();
+        app.send_tokens(
+            Addr::unchecked(OWNER),
+            dao_addr.clone(),
+            &coins(48, "ustars"),
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(SOMEBODY),
+            dao_addr,
+            &ExecuteMsg::Distribute {},
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            app.wrap().query_balance(VOTER4, "ustars").unwrap(),
+            coin(12, "ustars")
+        );
+        assert_eq!(
+            app.wrap().query_balance(group.addr(), "ustars").unwrap(),
+            coin(24, "ustars")
+        );
+    }
+}
@@ -1,6 +1,11 @@
 pub mod contract;
 mod error;
+pub mod integration_auction_tests;
+#[cfg(test)]
+mod integration_helpers;
 pub mod integration_nft_group_tests;
+pub mod integration_sub_dao_tests;
+pub mod integration_treasury_tests;
 pub mod msg;
 pub mod state;
 
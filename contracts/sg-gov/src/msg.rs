@@ -1,11 +1,17 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{CosmosMsg, Empty};
-use cw3::Vote;
+use cosmwasm_std::{Addr, Binary, Coin, CosmosMsg, Decimal, Timestamp, Uint128};
+use cw20::{Cw20ReceiveMsg, Denom};
+use cw3::{Status, Vote};
 use cw4::Cw4Contract;
-use cw_utils::{Duration, Expiration, Threshold};
-use sg_daos::ContractInstantiateMsg;
+use cw721::Cw721ReceiveMsg;
+use cw_utils::{Duration, Expiration, Threshold, ThresholdResponse};
+use sg_daos::{swap::Asset, ContractInstantiateMsg, RagequitMsg};
+use sg_std::StargazeMsgWrapper;
 
-use crate::state::Executor;
+use crate::state::{
+    Auction, ExecutionMode, Executor, MessageResult, ProposalAction, ProposalCategory,
+    ProposalMetadata, RevenueSplit, Stream,
+};
 
 #[cw_serde]
 pub enum Group {
@@ -21,9 +27,30 @@ pub struct InstantiateMsg {
     pub group: Group,
     pub threshold: Threshold,
     pub max_voting_period: Duration,
+    /// Proposals cannot pass before this period is over, even if they reach the threshold.
+    /// Must use the same unit as `max_voting_period` and not be longer than it.
+    pub min_voting_period: Option<Duration>,
+    /// Passed proposals cannot be executed before this period is over, giving members
+    /// time to ragequit. Must use the same unit as `max_voting_period`.
+    pub grace_period: Option<Duration>,
     /// who is able to execute passed proposals
     /// None means that anyone can execute
     pub executor: Option<Executor>,
+    /// DAO able to act as this one and dissolve it, set when created as a sub-DAO
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// DAO registry this DAO registers itself with
+    #[serde(default)]
+    pub registry: Option<String>,
+}
+
+/// Vote the proposer casts when creating a proposal
+#[cw_serde]
+pub enum ProposerVote {
+    Cast(Vote),
+    /// Propose without voting, e.g. on behalf of others or for a poll,
+    /// the proposer can still vote later
+    Skip {},
 }
 
 #[cw_serde]
@@ -31,14 +58,29 @@ pub enum ExecuteMsg {
     Propose {
         title: String,
         description: String,
-        // TODO: Empty needs to be StargazeMsgWrapper for Stargaze proposals like creating a collection?
-        msgs: Vec<CosmosMsg<Empty>>,
+        /// may include Stargaze custom messages, like claims or funding the fair burn pool
+        msgs: Vec<CosmosMsg<StargazeMsgWrapper>>,
+        /// typed actions, executed before `msgs`
+        #[serde(default)]
+        actions: Vec<ProposalAction>,
+        /// category, discussion link and off-chain content of the proposal
+        metadata: Option<ProposalMetadata>,
         // note: we ignore API-spec'd earliest if passed, always opens immediately
         latest: Option<Expiration>,
+        /// defaults to atomic execution
+        execution_mode: Option<ExecutionMode>,
+        /// defaults to voting yes
+        proposer_vote: Option<ProposerVote>,
     },
     Vote {
         proposal_id: u64,
         vote: Vote,
+        /// Optional reason for the vote, published alongside the ballot
+        rationale: Option<String>,
+    },
+    /// Submit votes signed off-chain by their voters
+    SubmitSignedVotes {
+        votes: Vec<SignedVote>,
     },
     Execute {
         proposal_id: u64,
@@ -51,6 +93,126 @@ pub enum ExecuteMsg {
         description: String,
         image: String,
     },
+    /// Replace the voting and execution rules (only via proposal)
+    UpdateConfig {
+        threshold: Threshold,
+        max_voting_period: Duration,
+        min_voting_period: Option<Duration>,
+        grace_period: Option<Duration>,
+        executor: Option<Executor>,
+    },
+    /// Delegate the sender's voting weight to another address
+    Delegate {
+        delegate: String,
+    },
+    /// Remove the sender's delegation
+    Undelegate {},
+    /// Receive an NFT into the treasury
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Receive cw20 tokens into the treasury, registering the token
+    Receive(Cw20ReceiveMsg),
+    /// Add or remove cw20 tokens tracked by the treasury (only via proposal)
+    UpdateTokenList {
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
+    /// Start paying `amount_per_interval` every `interval` seconds from `start` until `end`
+    /// (only via proposal)
+    CreateStream {
+        recipient: String,
+        denom: Denom,
+        amount_per_interval: Uint128,
+        interval: u64,
+        start: Timestamp,
+        end: Timestamp,
+        cliff: Option<Timestamp>,
+    },
+    /// Pay out what has vested so far to the stream recipient
+    ClaimStream {
+        id: u64,
+    },
+    /// Stop a stream, paying out what has vested so far (only via proposal)
+    CancelStream {
+        id: u64,
+    },
+    /// Let `grantee` spend up to `cap` of `denom` every `period` (only via proposal)
+    SetAllowance {
+        grantee: String,
+        denom: Denom,
+        cap: Uint128,
+        period: Duration,
+    },
+    /// Remove the allowance of `grantee` for `denom` (only via proposal)
+    RevokeAllowance {
+        grantee: String,
+        denom: Denom,
+    },
+    /// Swap treasury funds through a Terraswap compatible pair, receiving at least
+    /// `min_receive` of the other asset (only via proposal)
+    Swap {
+        pair: String,
+        offer: Asset,
+        min_receive: Uint128,
+    },
+    /// Pay a member leaving through the group their share of the treasury (only via the group)
+    Ragequit(RagequitMsg),
+    /// Auction an NFT held by the treasury for `denom` (only via proposal)
+    StartAuction {
+        collection: String,
+        token_id: String,
+        denom: String,
+        reserve_price: Uint128,
+        duration: Duration,
+    },
+    /// Bid the sent funds, refunding the previous highest bidder
+    Bid {
+        auction_id: u64,
+    },
+    /// Transfer the NFT to the highest bidder once the auction ended,
+    /// or take it back into the treasury if nobody bid
+    SettleAuction {
+        auction_id: u64,
+    },
+    /// Stop an auction, refunding the highest bid and taking the NFT back into
+    /// the treasury (only via proposal)
+    CancelAuction {
+        auction_id: u64,
+    },
+    /// Set or remove the revenue split policy (only via proposal)
+    UpdateRevenueSplit {
+        split: Option<RevenueSplit>,
+    },
+    /// Forward the group share of funds received since the last distribution
+    /// to the group and distribute them to its members
+    Distribute {},
+    /// Pay out of the treasury within the sender's allowance
+    Spend {
+        to: String,
+        denom: Denom,
+        amount: Uint128,
+    },
+    /// Instantiate a child sg-gov administered by this DAO (only via proposal).
+    /// `msg` must be an sg-gov `InstantiateMsg`, its parent is set to this DAO.
+    /// Budgets of sub-DAOs are granted with `SetAllowance`.
+    CreateSubDao(ContractInstantiateMsg),
+    /// Execute `msgs` as the sub-DAO `addr` (only via proposal)
+    OverrideSubDao {
+        addr: String,
+        msgs: Vec<CosmosMsg<StargazeMsgWrapper>>,
+    },
+    /// Dissolve the sub-DAO `addr`, returning its treasury and revoking its budgets
+    /// (only via proposal)
+    DissolveSubDao {
+        addr: String,
+    },
+    /// Execute `msgs` as this DAO (only via the parent)
+    ParentExecute {
+        msgs: Vec<CosmosMsg<StargazeMsgWrapper>>,
+    },
+    /// Send the treasury to the parent and stop accepting proposals (only via the parent).
+    /// NFTs stay in the treasury and can be moved with `ParentExecute`.
+    /// The DAO is removed from its registry, if any.
+    Dissolve {},
 }
 
 #[cw_serde]
@@ -58,26 +220,52 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(cw_utils::ThresholdResponse)]
     Threshold {},
-    #[returns(cw3::ProposalResponse)]
+    #[returns(ProposalResponse)]
     Proposal { proposal_id: u64 },
-    #[returns(cw3::ProposalListResponse)]
+    #[returns(ProposalListResponse)]
     ListProposals {
         start_after: Option<u64>,
         limit: Option<u32>,
+        /// only list proposals of this category
+        category: Option<ProposalCategory>,
+    },
+    /// Proposals with the given current status and proposer, in ascending id order
+    #[returns(ProposalListResponse)]
+    ListProposalsFiltered {
+        status: Option<Status>,
+        proposer: Option<String>,
+        /// only list proposals executed in best effort mode with this outcome,
+        /// failed executions are still listed with the Executed status
+        execution_status: Option<ExecutionStatus>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
     },
-    #[returns(cw3::ProposalListResponse)]
+    #[returns(ProposalListResponse)]
     ReverseProposals {
         start_before: Option<u64>,
         limit: Option<u32>,
     },
-    #[returns(cw3::VoteResponse)]
+    #[returns(VoteResponse)]
     Vote { proposal_id: u64, voter: String },
-    #[returns(cw3::VoteListResponse)]
+    #[returns(VoteListResponse)]
     ListVotes {
         proposal_id: u64,
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Votes of `voter` across proposals, by ascending proposal id
+    #[returns(VoteListResponse)]
+    ListVotesByVoter {
+        voter: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Turnout of the latest proposals and its average
+    #[returns(ParticipationStatsResponse)]
+    ParticipationStats {},
+    /// Number of proposals `address` voted on
+    #[returns(MemberParticipationResponse)]
+    MemberParticipation { address: String },
     #[returns(cw3::VoterResponse)]
     Voter { address: String },
     #[returns(cw3::VoterListResponse)]
@@ -89,6 +277,142 @@ pub enum QueryMsg {
     Group {},
     #[returns(MetadataResponse)]
     Metadata {},
+    #[returns(DelegationResponse)]
+    Delegation { delegator: String },
+    #[returns(DelegationListResponse)]
+    ListDelegations {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the weight an address votes with at the given height,
+    /// including the weight delegated to it
+    #[returns(VotingPowerResponse)]
+    VotingPower {
+        address: String,
+        at_height: Option<u64>,
+    },
+    /// NFTs of a collection held by the treasury
+    #[returns(NftListResponse)]
+    ListNfts {
+        collection: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Collections the treasury holds NFTs of
+    #[returns(CollectionListResponse)]
+    ListCollections {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Native balances and registered cw20 balances of the treasury.
+    /// Pagination applies to the cw20 tokens, native balances are returned on every page.
+    #[returns(TreasuryBalancesResponse)]
+    TreasuryBalances {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(StreamResponse)]
+    Stream { id: u64 },
+    #[returns(StreamListResponse)]
+    ListStreams {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Remaining allowance of `grantee` for `denom` in the current period
+    #[returns(AllowanceResponse)]
+    Allowance { grantee: String, denom: Denom },
+    /// All allowances of `grantee`
+    #[returns(AllowanceListResponse)]
+    ListAllowances { grantee: String },
+    /// Sub-DAOs created by this DAO with their budgets
+    #[returns(SubDaoListResponse)]
+    ListSubDaos {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Parent of this DAO, if it is a sub-DAO
+    #[returns(ParentResponse)]
+    Parent {},
+    #[returns(AuctionResponse)]
+    Auction { id: u64 },
+    #[returns(AuctionListResponse)]
+    ListAuctions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(RevenueSplitResponse)]
+    RevenueSplit {},
+    /// Checks what the messages of a proposal would do against the current state,
+    /// returning warnings for messages that look like they would fail
+    #[returns(SimulationResponse)]
+    SimulateProposal { proposal_id: u64 },
+    /// Results of a proposal executed in best effort mode
+    #[returns(ExecutionResultsResponse)]
+    ExecutionResults { proposal_id: u64 },
+    /// Returns the nonce the next signed vote of an address must use
+    #[returns(NonceResponse)]
+    Nonce { address: String },
+}
+
+/// The message a voter signs to vote without sending a transaction
+#[cw_serde]
+pub struct SignedVotePayload {
+    pub contract: String,
+    pub proposal_id: u64,
+    pub vote: Vote,
+    pub nonce: u64,
+}
+
+#[cw_serde]
+pub struct SignedVote {
+    pub payload: SignedVotePayload,
+    /// Compressed secp256k1 public key of the voter
+    pub pubkey: Binary,
+    /// Signature over the sha256 hash of the JSON encoded payload
+    pub signature: Binary,
+}
+
+#[cw_serde]
+pub struct NonceResponse {
+    pub nonce: u64,
+}
+
+#[cw_serde]
+pub struct VoteInfo {
+    pub proposal_id: u64,
+    pub voter: String,
+    pub vote: Vote,
+    pub weight: u64,
+    pub rationale: Option<String>,
+}
+
+#[cw_serde]
+pub struct VoteResponse {
+    pub vote: Option<VoteInfo>,
+}
+
+#[cw_serde]
+pub struct VoteListResponse {
+    pub votes: Vec<VoteInfo>,
+}
+
+#[cw_serde]
+pub struct ProposalTurnout {
+    pub proposal_id: u64,
+    /// weight that voted over the total weight when the proposal was created
+    pub turnout: Decimal,
+}
+
+#[cw_serde]
+pub struct ParticipationStatsResponse {
+    /// latest proposals first
+    pub turnouts: Vec<ProposalTurnout>,
+    pub average_turnout: Decimal,
+}
+
+#[cw_serde]
+pub struct MemberParticipationResponse {
+    pub proposals_voted: u64,
 }
 
 #[cw_serde]
@@ -102,3 +426,176 @@ pub struct MetadataResponse {
     pub description: String,
     pub image: String,
 }
+
+#[cw_serde]
+pub struct DelegationResponse {
+    pub delegate: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct Delegation {
+    pub delegator: Addr,
+    pub delegate: Addr,
+}
+
+#[cw_serde]
+pub struct DelegationListResponse {
+    pub delegations: Vec<Delegation>,
+}
+
+#[cw_serde]
+pub struct VotingPowerResponse {
+    /// Weight of the address in the group
+    pub weight: u64,
+    /// Weight delegated to the address by other members
+    pub delegated: u64,
+    pub total: u64,
+}
+
+#[cw_serde]
+pub struct NftListResponse {
+    pub tokens: Vec<String>,
+}
+
+#[cw_serde]
+pub struct CollectionListResponse {
+    pub collections: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct Cw20Balance {
+    pub address: Addr,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct TreasuryBalancesResponse {
+    pub native: Vec<Coin>,
+    pub cw20: Vec<Cw20Balance>,
+    /// registered tokens whose balance could not be queried
+    pub unavailable: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct StreamResponse {
+    pub id: u64,
+    pub stream: Stream,
+    /// amount the recipient can claim right now
+    pub claimable: Uint128,
+}
+
+#[cw_serde]
+pub struct StreamListResponse {
+    pub streams: Vec<StreamResponse>,
+}
+
+#[cw_serde]
+pub struct AllowanceResponse {
+    pub denom: Denom,
+    pub cap: Uint128,
+    pub period: Duration,
+    pub remaining: Uint128,
+    /// when `remaining` goes back to `cap`
+    pub resets: Expiration,
+}
+
+#[cw_serde]
+pub struct AllowanceListResponse {
+    pub allowances: Vec<AllowanceResponse>,
+}
+
+#[cw_serde]
+pub struct SubDaoResponse {
+    pub addr: Addr,
+    /// allowances granted to the sub-DAO
+    pub budgets: Vec<AllowanceResponse>,
+}
+
+#[cw_serde]
+pub struct SubDaoListResponse {
+    pub sub_daos: Vec<SubDaoResponse>,
+}
+
+#[cw_serde]
+pub struct ParentResponse {
+    pub parent: Option<Addr>,
+    pub dissolved: bool,
+}
+
+#[cw_serde]
+pub struct AuctionResponse {
+    pub id: u64,
+    pub auction: Auction,
+}
+
+#[cw_serde]
+pub struct AuctionListResponse {
+    pub auctions: Vec<AuctionResponse>,
+}
+
+#[cw_serde]
+pub struct RevenueSplitResponse {
+    pub split: Option<RevenueSplit>,
+    /// funds received since the last distribution
+    pub pending: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct MessageSimulation {
+    pub warnings: Vec<String>,
+}
+
+#[cw_serde]
+pub struct SimulationResponse {
+    /// one entry per proposal message, in order
+    pub messages: Vec<MessageSimulation>,
+}
+
+/// Outcome of a best effort execution
+#[cw_serde]
+pub enum ExecutionStatus {
+    Succeeded,
+    /// at least one message failed
+    ExecutionFailed,
+}
+
+#[cw_serde]
+pub struct ExecutionResultsResponse {
+    pub mode: ExecutionMode,
+    /// None until a best effort proposal is executed
+    pub status: Option<ExecutionStatus>,
+    /// one entry per proposal message, in order
+    pub results: Vec<MessageResult>,
+}
+
+/// A typed action of a proposal with a human-readable summary
+#[cw_serde]
+pub struct ActionResponse {
+    pub action: ProposalAction,
+    pub summary: String,
+}
+
+/// Same as cw3's proposal response, with the typed actions and metadata of the proposal
+#[cw_serde]
+pub struct ProposalResponse {
+    pub id: u64,
+    pub proposer: Addr,
+    pub title: String,
+    pub description: String,
+    pub msgs: Vec<CosmosMsg<StargazeMsgWrapper>>,
+    pub actions: Vec<ActionResponse>,
+    pub metadata: Option<ProposalMetadata>,
+    /// stays Executed when messages of a best effort execution failed,
+    /// `execution_status` tells whether they all succeeded
+    pub status: Status,
+    /// outcome of a best effort execution, None until then or when executed atomically
+    /// since a failing atomic execution is reverted
+    pub execution_status: Option<ExecutionStatus>,
+    pub expires: Expiration,
+    pub threshold: ThresholdResponse,
+}
+
+#[cw_serde]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
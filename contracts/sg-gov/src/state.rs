@@ -1,8 +1,15 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, QuerierWrapper, Storage};
+use cosmwasm_std::{
+    Addr, BlockInfo, Coin, Decimal, Empty, QuerierWrapper, StdResult, Storage, Timestamp, Uint128,
+};
+use cw20::Denom;
+use cw3::Status;
+use cw3_fixed_multisig::state::Votes;
 use cw4::Cw4Contract;
-use cw_storage_plus::Item;
-use cw_utils::{Duration, Threshold};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotMap, Strategy};
+use cw_utils::{Duration, Expiration, Threshold};
+use sg_daos::{Admin, ContractInstantiateMsg};
+use sg_std::CosmosMsg;
 
 use crate::ContractError;
 
@@ -23,6 +30,10 @@ pub struct Config {
 
     pub threshold: Threshold,
     pub max_voting_period: Duration,
+    // proposals cannot pass before this period is over
+    pub min_voting_period: Option<Duration>,
+    // passed proposals cannot be executed before this period is over
+    pub grace_period: Option<Duration>,
     // who is able to execute passed proposals
     // None means that anyone can execute
     pub executor: Option<Executor>,
@@ -58,9 +69,618 @@ impl Config {
     }
 }
 
+// we multiply by this when calculating needed_votes in order to round up properly
+const PRECISION_FACTOR: u128 = 1_000_000_000;
+
+// same rounding as cw3-fixed-multisig: we need 8, not 7 votes to reach 50% of 15 total
+fn votes_needed(weight: u64, percentage: Decimal) -> u64 {
+    let applied = percentage * Uint128::new(PRECISION_FACTOR * weight as u128);
+    let rounded = (applied + Uint128::new(PRECISION_FACTOR - 1)) / Uint128::new(PRECISION_FACTOR);
+    rounded.u128() as u64
+}
+
+/// Typed alternative to raw messages for common treasury and governance operations,
+/// converted to messages when the proposal is executed
+#[cw_serde]
+pub enum ProposalAction {
+    /// Send native or cw20 tokens out of the treasury
+    SendTokens {
+        recipient: String,
+        denom: Denom,
+        amount: Uint128,
+    },
+    /// Transfer an NFT held by the treasury
+    TransferNft {
+        collection: String,
+        token_id: String,
+        recipient: String,
+    },
+    /// Instantiate a contract, `Admin::Creator` makes the DAO its admin
+    InstantiateContract(ContractInstantiateMsg),
+    UpdateMetadata {
+        name: String,
+        description: String,
+        image: String,
+    },
+    UpdateConfig {
+        threshold: Threshold,
+        max_voting_period: Duration,
+        min_voting_period: Option<Duration>,
+        grace_period: Option<Duration>,
+        executor: Option<Executor>,
+    },
+}
+
+fn duration_summary(duration: &Duration) -> String {
+    match duration {
+        Duration::Height(height) => format!("{} blocks", height),
+        Duration::Time(time) => format!("{} seconds", time),
+    }
+}
+
+fn threshold_summary(threshold: &Threshold) -> String {
+    match threshold {
+        Threshold::AbsoluteCount { weight } => format!("{} yes votes", weight),
+        Threshold::AbsolutePercentage { percentage } => {
+            format!("{} of the weight voting yes", percentage)
+        }
+        Threshold::ThresholdQuorum { threshold, quorum } => format!(
+            "{} of the votes cast voting yes with a quorum of {}",
+            threshold, quorum
+        ),
+    }
+}
+
+impl ProposalAction {
+    /// Human-readable description of what the action does
+    pub fn summary(&self) -> String {
+        match self {
+            ProposalAction::SendTokens {
+                recipient,
+                denom,
+                amount,
+            } => match denom {
+                Denom::Native(denom) => format!("Send {}{} to {}", amount, denom, recipient),
+                Denom::Cw20(token) => {
+                    format!("Send {} of token {} to {}", amount, token, recipient)
+                }
+            },
+            ProposalAction::TransferNft {
+                collection,
+                token_id,
+                recipient,
+            } => format!(
+                "Transfer NFT {} of {} to {}",
+                token_id, collection, recipient
+            ),
+            ProposalAction::InstantiateContract(init) => {
+                let admin = match &init.admin {
+                    Some(Admin::Address { addr }) => format!("{} as admin", addr),
+                    Some(Admin::Creator {}) => "the DAO as admin".to_string(),
+                    None => "no admin".to_string(),
+                };
+                format!(
+                    "Instantiate code {} labeled \"{}\" with {}",
+                    init.code_id, init.label, admin
+                )
+            }
+            ProposalAction::UpdateMetadata {
+                name,
+                description,
+                image,
+            } => format!(
+                "Update the DAO name to \"{}\", its description to \"{}\" and its image to {}",
+                name, description, image
+            ),
+            ProposalAction::UpdateConfig {
+                threshold,
+                max_voting_period,
+                min_voting_period,
+                grace_period,
+                executor,
+            } => {
+                let mut parts = vec![
+                    format!("pass with {}", threshold_summary(threshold)),
+                    format!("vote for at most {}", duration_summary(max_voting_period)),
+                ];
+                if let Some(min_voting_period) = min_voting_period {
+                    parts.push(format!(
+                        "vote for at least {}",
+                        duration_summary(min_voting_period)
+                    ));
+                }
+                if let Some(grace_period) = grace_period {
+                    parts.push(format!(
+                        "wait {} before execution",
+                        duration_summary(grace_period)
+                    ));
+                }
+                parts.push(match executor {
+                    Some(Executor::Member) => "let members execute".to_string(),
+                    Some(Executor::Only(addr)) => format!("let only {} execute", addr),
+                    None => "let anyone execute".to_string(),
+                });
+                format!("Update the DAO config to {}", parts.join(", "))
+            }
+        }
+    }
+}
+
+/// Maximum length of the discussion URL of a proposal
+pub const MAX_DISCUSSION_URL_LENGTH: usize = 256;
+
+/// Maximum length of the content hash of a proposal
+pub const MAX_CONTENT_HASH_LENGTH: usize = 128;
+
+#[cw_serde]
+pub enum ProposalCategory {
+    Treasury,
+    Governance,
+    Membership,
+    Collection,
+    Other,
+}
+
+/// Structured information published with a proposal
+#[cw_serde]
+pub struct ProposalMetadata {
+    pub category: ProposalCategory,
+    /// Where the proposal is discussed off-chain
+    pub discussion_url: Option<String>,
+    /// Hash of the long-form specification stored off-chain, like an IPFS CID
+    pub content_hash: Option<String>,
+}
+
+impl ProposalMetadata {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if let Some(url) = &self.discussion_url {
+            if url.is_empty() || url.len() > MAX_DISCUSSION_URL_LENGTH {
+                return Err(ContractError::InvalidMetadata {
+                    field: "discussion_url".to_string(),
+                    max: MAX_DISCUSSION_URL_LENGTH,
+                });
+            }
+        }
+        if let Some(hash) = &self.content_hash {
+            if hash.is_empty() || hash.len() > MAX_CONTENT_HASH_LENGTH {
+                return Err(ContractError::InvalidMetadata {
+                    field: "content_hash".to_string(),
+                    max: MAX_CONTENT_HASH_LENGTH,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A proposal whose messages can be Stargaze custom messages,
+/// like claims or funding the community and fair burn pools
+#[cw_serde]
+pub struct Proposal {
+    pub title: String,
+    pub description: String,
+    pub proposer: Addr,
+    pub start_height: u64,
+    pub expires: Expiration,
+    pub msgs: Vec<CosmosMsg>,
+    /// executed before `msgs`
+    pub actions: Vec<ProposalAction>,
+    pub metadata: Option<ProposalMetadata>,
+    pub status: Status,
+    /// pass requirements
+    pub threshold: Threshold,
+    // the total weight when the proposal started (used to calculate percentages)
+    pub total_weight: u64,
+    // summary of existing votes
+    pub votes: Votes,
+}
+
+impl Proposal {
+    /// current_status is non-mutable and returns what the status should be.
+    /// (designed for queries)
+    pub fn current_status(&self, block: &BlockInfo) -> Status {
+        let mut status = self.status;
+
+        // if open, check if voting is passed or timed out
+        if status == Status::Open && self.is_passed(block) {
+            status = Status::Passed;
+        }
+        if status == Status::Open && (self.is_rejected(block) || self.expires.is_expired(block)) {
+            status = Status::Rejected;
+        }
+
+        status
+    }
+
+    /// Returns true if this proposal is sure to pass (even before expiration, if no future
+    /// sequence of possible votes could cause it to fail).
+    pub fn is_passed(&self, block: &BlockInfo) -> bool {
+        match self.threshold {
+            Threshold::AbsoluteCount {
+                weight: weight_needed,
+            } => self.votes.yes >= weight_needed,
+            Threshold::AbsolutePercentage {
+                percentage: percentage_needed,
+            } => {
+                self.votes.yes
+                    >= votes_needed(self.total_weight - self.votes.abstain, percentage_needed)
+            }
+            Threshold::ThresholdQuorum { threshold, quorum } => {
+                // we always require the quorum
+                if self.votes.total() < votes_needed(self.total_weight, quorum) {
+                    return false;
+                }
+                if self.expires.is_expired(block) {
+                    // If expired, we compare vote_count against the total number of votes (minus abstain).
+                    let opinions = self.votes.total() - self.votes.abstain;
+                    self.votes.yes >= votes_needed(opinions, threshold)
+                } else {
+                    // If not expired, we must assume all non-votes will be cast against
+                    let possible_opinions = self.total_weight - self.votes.abstain;
+                    self.votes.yes >= votes_needed(possible_opinions, threshold)
+                }
+            }
+        }
+    }
+
+    /// Returns true if this proposal is sure to be rejected (even before expiration, if
+    /// no future sequence of possible votes could cause it to pass).
+    pub fn is_rejected(&self, block: &BlockInfo) -> bool {
+        match self.threshold {
+            Threshold::AbsoluteCount {
+                weight: weight_needed,
+            } => {
+                let weight = self.total_weight - weight_needed;
+                self.votes.no > weight
+            }
+            Threshold::AbsolutePercentage {
+                percentage: percentage_needed,
+            } => {
+                self.votes.no
+                    > votes_needed(
+                        self.total_weight - self.votes.abstain,
+                        Decimal::one() - percentage_needed,
+                    )
+            }
+            Threshold::ThresholdQuorum { threshold, .. } => {
+                if self.expires.is_expired(block) {
+                    // If expired, we compare vote_count against the total number of votes (minus abstain).
+                    let opinions = self.votes.total() - self.votes.abstain;
+                    self.votes.no > votes_needed(opinions, Decimal::one() - threshold)
+                } else {
+                    // If not expired, we must assume all non-votes will be cast for
+                    let possible_opinions = self.total_weight - self.votes.abstain;
+                    self.votes.no > votes_needed(possible_opinions, Decimal::one() - threshold)
+                }
+            }
+        }
+    }
+}
+
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+
+pub fn next_proposal_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = PROPOSAL_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    PROPOSAL_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+pub struct ProposalIndexes<'a> {
+    /// Stored status, open proposals are only updated when voted on, executed or closed
+    pub status: MultiIndex<'a, u8, Proposal, u64>,
+    pub proposer: MultiIndex<'a, Addr, Proposal, u64>,
+}
+
+impl<'a> IndexList<Proposal> for ProposalIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Proposal>> + '_> {
+        let v: Vec<&dyn Index<Proposal>> = vec![&self.status, &self.proposer];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Proposals by id, indexed by their stored status and proposer
+pub fn proposals<'a>() -> IndexedMap<'a, u64, Proposal, ProposalIndexes<'a>> {
+    let indexes = ProposalIndexes {
+        status: MultiIndex::new(
+            |_, prop| prop.status as u8,
+            "proposals",
+            "proposals__status",
+        ),
+        proposer: MultiIndex::new(
+            |_, prop| prop.proposer.clone(),
+            "proposals",
+            "proposals__proposer",
+        ),
+    };
+    IndexedMap::new("proposals", indexes)
+}
+
+/// Returns true if the proposal can no longer reach its threshold,
+/// even if all the weight that has not voted yet votes yes
+pub fn cannot_pass(prop: &Proposal) -> bool {
+    let votes = &prop.votes;
+    let max_yes = votes.yes + prop.total_weight.saturating_sub(votes.total());
+    let needed = match prop.threshold {
+        Threshold::AbsoluteCount { weight } => weight,
+        Threshold::AbsolutePercentage { percentage } => {
+            votes_needed(prop.total_weight - votes.abstain, percentage)
+        }
+        Threshold::ThresholdQuorum { threshold, .. } => {
+            votes_needed(prop.total_weight - votes.abstain, threshold)
+        }
+    };
+    max_yes < needed
+}
+
+/// Same as `Proposal::current_status`, but also rejects open proposals
+/// that can no longer pass, and keeps proposals open until their minimum voting period is over
+pub fn proposal_status(
+    storage: &dyn Storage,
+    id: u64,
+    prop: &Proposal,
+    block: &BlockInfo,
+) -> StdResult<Status> {
+    let status = prop.current_status(block);
+    if status == Status::Open && cannot_pass(prop) {
+        return Ok(Status::Rejected);
+    }
+    if status == Status::Passed {
+        let min_voting_end = MIN_VOTING_ENDS.may_load(storage, id)?;
+        if matches!(min_voting_end, Some(end) if !end.is_expired(block)) {
+            return Ok(Status::Open);
+        }
+    }
+    Ok(status)
+}
+
 /// Unique items
 pub const CONFIG: Item<Config> = Item::new("config");
 
 /// The group that holds DAO members
 /// Total weight and voters are queried from this contract
 pub const GROUP: Item<Cw4Contract> = Item::new("group");
+
+/// proposal_id -> end of the minimum voting period of the proposal
+pub const MIN_VOTING_ENDS: Map<u64, Expiration> = Map::new("min_voting_ends");
+
+/// proposal_id -> end of the grace period of a proposal that passed by votes
+pub const GRACE_ENDS: Map<u64, Expiration> = Map::new("grace_ends");
+
+/// Maximum length of a vote rationale
+pub const MAX_RATIONALE_LENGTH: usize = 1024;
+
+/// (proposal_id, voter) -> rationale published with the ballot
+pub const RATIONALES: Map<(u64, &Addr), String> = Map::new("rationales");
+
+/// (voter, proposal_id) of every ballot, to list the votes of a voter across proposals
+pub const VOTER_BALLOTS: Map<(&Addr, u64), Empty> = Map::new("voter_ballots");
+
+/// proposal_id -> weight that voted over the total weight when the proposal was created
+pub const TURNOUTS: Map<u64, Decimal> = Map::new("turnouts");
+
+/// Voter -> number of proposals they voted on, including the ones they proposed
+pub const PROPOSALS_VOTED: Map<&Addr, u64> = Map::new("proposals_voted");
+
+/// Number of latest proposals averaged in the participation stats
+pub const PARTICIPATION_WINDOW: usize = 20;
+
+pub fn record_turnout(storage: &mut dyn Storage, id: u64, prop: &Proposal) -> StdResult<()> {
+    let turnout = if prop.total_weight == 0 {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(prop.votes.total(), prop.total_weight)
+    };
+    TURNOUTS.save(storage, id, &turnout)
+}
+
+/// Voter -> nonce expected in their next signed vote
+pub const NONCES: Map<&Addr, u64> = Map::new("nonces");
+
+/// Delegator -> delegate
+/// Snapshotted so a delegation is always read as of the proposal start height
+pub const DELEGATIONS: SnapshotMap<&Addr, Addr> = SnapshotMap::new(
+    "delegations",
+    "delegations__checkpoints",
+    "delegations__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Delegate -> delegators
+pub const DELEGATORS: SnapshotMap<&Addr, Vec<Addr>> = SnapshotMap::new(
+    "delegators",
+    "delegators__checkpoints",
+    "delegators__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Weight a delegate has cast on behalf of a delegator
+#[cw_serde]
+pub struct DelegatedVote {
+    pub delegate: Addr,
+    pub weight: u64,
+}
+
+/// (delegator, proposal_id) -> vote cast by the delegate
+/// Used to take back the weight if the delegator later votes directly
+pub const DELEGATED_VOTES: Map<(&Addr, u64), DelegatedVote> = Map::new("delegated_votes");
+
+/// (collection, token_id) of NFTs held by the treasury
+pub const NFTS: Map<(&Addr, &str), Empty> = Map::new("nfts");
+
+/// Collection -> number of its NFTs held by the treasury
+pub const COLLECTIONS: Map<&Addr, u64> = Map::new("collections");
+
+/// cw20 tokens whose balances are reported for the treasury
+pub const TOKENS: Map<&Addr, Empty> = Map::new("tokens");
+
+/// Funds vesting from the treasury to a recipient over time
+#[cw_serde]
+pub struct Stream {
+    pub recipient: Addr,
+    pub denom: Denom,
+    pub amount_per_interval: Uint128,
+    /// interval length in seconds
+    pub interval: u64,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    /// nothing can be claimed before the cliff
+    pub cliff: Option<Timestamp>,
+    pub claimed: Uint128,
+}
+
+impl Stream {
+    /// Total amount vested at the given time, including what was already claimed
+    pub fn vested(&self, time: Timestamp) -> Uint128 {
+        if time < self.cliff.unwrap_or(self.start) {
+            return Uint128::zero();
+        }
+        let elapsed = time.min(self.end).seconds() - self.start.seconds();
+        self.amount_per_interval * Uint128::from(elapsed / self.interval)
+    }
+
+    pub fn claimable(&self, time: Timestamp) -> Uint128 {
+        self.vested(time) - self.claimed
+    }
+}
+
+pub const STREAM_COUNT: Item<u64> = Item::new("stream_count");
+pub const STREAMS: Map<u64, Stream> = Map::new("streams");
+
+pub fn next_stream_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = STREAM_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    STREAM_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+/// Amount of a denom a grantee may spend from the treasury per period
+#[cw_serde]
+pub struct Allowance {
+    pub denom: Denom,
+    pub cap: Uint128,
+    pub period: Duration,
+    /// spent in the current period
+    pub spent: Uint128,
+    /// end of the current period
+    pub resets: Expiration,
+}
+
+impl Allowance {
+    /// Starts a new period if the current one is over
+    pub fn refresh(&mut self, block: &BlockInfo) {
+        if self.resets.is_expired(block) {
+            self.spent = Uint128::zero();
+            self.resets = self.period.after(block);
+        }
+    }
+}
+
+/// (grantee, denom key) -> allowance
+pub const ALLOWANCES: Map<(&Addr, &str), Allowance> = Map::new("allowances");
+
+/// Storage key of a denom, native denoms and cw20 addresses cannot collide
+pub fn denom_key(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => format!("native:{}", denom),
+        Denom::Cw20(address) => format!("cw20:{}", address),
+    }
+}
+
+#[cw_serde]
+pub struct Bid {
+    pub bidder: Addr,
+    pub amount: Uint128,
+}
+
+/// English auction of a treasury NFT, paid in a native denom
+#[cw_serde]
+pub struct Auction {
+    pub collection: Addr,
+    pub token_id: String,
+    pub denom: String,
+    /// bids below this are rejected
+    pub reserve_price: Uint128,
+    pub end: Expiration,
+    /// escrowed by the contract until outbid or settled
+    pub highest_bid: Option<Bid>,
+}
+
+pub const AUCTION_COUNT: Item<u64> = Item::new("auction_count");
+pub const AUCTIONS: Map<u64, Auction> = Map::new("auctions");
+
+/// denom -> sum of the highest bids escrowed in running auctions
+pub const ESCROWED_BIDS: Map<&str, Uint128> = Map::new("escrowed_bids");
+
+/// (collection, token_id) -> id of the running auction of the NFT
+pub const AUCTIONED_NFTS: Map<(&Addr, &str), u64> = Map::new("auctioned_nfts");
+
+/// Auction whose NFT is being transferred to the winner,
+/// the winning bid is refunded if the transfer fails
+pub const SETTLING_AUCTION: Item<Auction> = Item::new("settling_auction");
+
+pub fn next_auction_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = AUCTION_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    AUCTION_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+/// Share of incoming treasury funds forwarded to the group for distribution to its members
+#[cw_serde]
+pub struct RevenueSplit {
+    /// between 0 and 1, the rest stays in the treasury
+    pub group_share: Decimal,
+    /// native denoms the split applies to
+    pub denoms: Vec<String>,
+}
+
+pub const REVENUE_SPLIT: Item<RevenueSplit> = Item::new("revenue_split");
+
+/// denom -> treasury balance already accounted for by the revenue split,
+/// lowered by funds leaving the treasury and raised by swap proceeds and auction sales
+pub const REVENUE_BASELINES: Map<&str, Uint128> = Map::new("revenue_baselines");
+
+/// Treasury balances of the revenue split denoms before a swap, without the offered funds
+pub const SWAP_BALANCES: Item<Vec<Coin>> = Item::new("swap_balances");
+
+/// How the messages of a passed proposal are dispatched
+#[cw_serde]
+#[derive(Default)]
+pub enum ExecutionMode {
+    /// All messages succeed or the whole execution is reverted
+    #[default]
+    Atomic,
+    /// Every message is dispatched on its own and failures are recorded instead of reverting
+    BestEffort,
+}
+
+#[cw_serde]
+pub enum MessageResult {
+    Success {},
+    Failure { error: String },
+}
+
+/// proposal_id -> execution mode, only stored for proposals not executed atomically
+pub const EXECUTION_MODES: Map<u64, ExecutionMode> = Map::new("execution_modes");
+
+/// (proposal_id, message index) -> result of a message executed in best effort mode
+pub const EXECUTION_RESULTS: Map<(u64, u32), MessageResult> = Map::new("execution_results");
+
+/// Reply ids of best effort executions carry the proposal id and the message index
+pub fn execution_reply_id(proposal_id: u64, index: u32) -> u64 {
+    proposal_id << 32 | index as u64
+}
+
+pub fn parse_execution_reply_id(id: u64) -> (u64, u32) {
+    (id >> 32, id as u32)
+}
+
+/// Registry listing this DAO, notified when its metadata changes
+pub const REGISTRY: Item<Addr> = Item::new("registry");
+
+/// DAO that created this one as a sub-DAO, able to act as it and dissolve it
+pub const PARENT: Item<Addr> = Item::new("parent");
+
+/// Set once the parent dissolved this DAO, no proposals can be created afterwards
+pub const DISSOLVED: Item<bool> = Item::new("dissolved");
+
+/// Sub-DAOs created by this DAO, their budgets are allowances of the treasury
+pub const SUB_DAOS: Map<&Addr, Empty> = Map::new("sub_daos");
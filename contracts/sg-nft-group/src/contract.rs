@@ -3,11 +3,12 @@ use std::marker::PhantomData;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, from_binary, to_binary, Addr, BankMsg, Binary, Decimal, Deps, DepsMut, Empty, Env,
+    coin, from_binary, to_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, Empty, Env,
     MessageInfo, Order, Reply, Response, StdResult, Storage, SubMsg, WasmMsg,
 };
 
 use cw2::set_contract_version;
+use cw20::Denom;
 use cw4::{Member, MemberListResponse, MemberResponse, TotalWeightResponse};
 use cw721::Cw721ReceiveMsg;
 use cw721_base::helpers::Cw721Contract;
@@ -17,7 +18,7 @@ use cw721_base::{
 };
 use cw_storage_plus::Bound;
 use cw_utils::{maybe_addr, parse_reply_instantiate_data};
-use sg_daos::ContractInstantiateMsg;
+use sg_daos::{ContractInstantiateMsg, RagequitMsg};
 
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
@@ -92,6 +93,10 @@ pub fn execute(
         ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, env, info, msg),
         ExecuteMsg::Remove { token_id } => execute_remove(deps, env, info, token_id),
         ExecuteMsg::Withdraw { denom } => execute_withdraw(deps, env, denom),
+        ExecuteMsg::Distribute {} => execute_distribute(deps, info),
+        ExecuteMsg::Ragequit { token_ids, denoms } => {
+            execute_ragequit(deps, env, info, token_ids, denoms)
+        }
     }
 }
 
@@ -151,30 +156,100 @@ pub fn execute_remove(
 }
 
 pub fn execute_withdraw(deps: DepsMut, env: Env, denom: String) -> Result<Response, ContractError> {
-    let total_weight = query_total_weight(deps.as_ref())?;
-    let members = list_members(deps.as_ref(), None, None)?;
-
     let funds = deps.querier.query_balance(env.contract.address, denom)?;
     if funds.amount.is_zero() {
         return Err(ContractError::NoFunds {});
     }
 
-    let msgs = members
-        .members
-        .iter()
-        .map(|member| {
-            let ratio = Decimal::from_ratio(member.weight, total_weight.weight);
-            let amount = funds.amount * ratio;
-            BankMsg::Send {
-                to_address: member.addr.clone(),
-                amount: coins(amount.u128(), funds.denom.clone()),
-            }
-        })
-        .collect::<Vec<_>>();
+    Ok(Response::new()
+        .add_attribute("action", "distribute")
+        .add_messages(pay_members(deps.as_ref(), &[funds])?))
+}
+
+pub fn execute_distribute(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    if info.funds.iter().all(|coin| coin.amount.is_zero()) {
+        return Err(ContractError::NoFunds {});
+    }
 
     Ok(Response::new()
         .add_attribute("action", "distribute")
-        .add_messages(msgs))
+        .add_messages(pay_members(deps.as_ref(), &info.funds)?))
+}
+
+/// Splits `funds` between all members by weight
+fn pay_members(deps: Deps, funds: &[Coin]) -> StdResult<Vec<BankMsg>> {
+    let total_weight = query_total_weight(deps)?;
+
+    // every member is paid, not only the first page of `list_members`
+    let mut msgs = vec![];
+    for item in MEMBERS.range(deps.storage, None, None, Order::Ascending) {
+        let (addr, weight) = item?;
+        let amount: Vec<Coin> = funds
+            .iter()
+            .map(|funds| {
+                coin(
+                    funds
+                        .amount
+                        .multiply_ratio(weight, total_weight.weight)
+                        .u128(),
+                    &funds.denom,
+                )
+            })
+            .filter(|coin| !coin.amount.is_zero())
+            .collect();
+        if amount.is_empty() {
+            continue;
+        }
+        msgs.push(BankMsg::Send {
+            to_address: addr.into_string(),
+            amount,
+        });
+    }
+    Ok(msgs)
+}
+
+pub fn execute_ragequit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_ids: Vec<String>,
+    denoms: Vec<Denom>,
+) -> Result<Response, ContractError> {
+    let member = info.sender;
+    if token_ids.is_empty() {
+        return Err(ContractError::NoTokens {});
+    }
+
+    // the DAO administering the group pays out and receives the original NFTs
+    let dao = deps
+        .querier
+        .query_wasm_contract_info(&env.contract.address)?
+        .admin
+        .ok_or(ContractError::NoDao {})?;
+    let dao = deps.api.addr_validate(&dao)?;
+
+    let member_collection = MEMBER_COLLECTION.load(deps.storage)?;
+    let mut msgs = vec![];
+    for token_id in &token_ids {
+        only_owner(deps.as_ref(), &member, &member_collection, token_id)?;
+        remove_member_weight(deps.storage, &member, env.block.height)?;
+        msgs.extend(surrender(deps.storage, token_id, &dao)?);
+    }
+
+    // the DAO pays out after the weight is gone from the group
+    let ragequit_msg = RagequitMsg {
+        member: member.to_string(),
+        weight: token_ids.len() as u64,
+        denoms,
+    }
+    .into_cosmos_msg(&dao)?;
+
+    Ok(Response::new()
+        .add_submessages(msgs)
+        .add_message(ragequit_msg)
+        .add_attribute("action", "ragequit")
+        .add_attribute("sender", member)
+        .add_attribute("dao", dao))
 }
 
 fn only_owner(
@@ -254,6 +329,30 @@ fn leave(store: &dyn Storage, token_id: &str, member: &str) -> StdResult<Vec<Sub
     Ok(vec![SubMsg::new(transfer_msg), SubMsg::new(burn_msg)])
 }
 
+/// To ragequit, the NFT is burned from the internal collection
+/// and the original one is sent to the DAO instead of the member.
+fn surrender(store: &dyn Storage, token_id: &str, dao: &Addr) -> StdResult<Vec<SubMsg>> {
+    let send_msg = WasmMsg::Execute {
+        contract_addr: CONFIG.load(store)?.collection.to_string(),
+        msg: to_binary(&Cw721BaseExecuteMsg::SendNft::<Empty, Empty> {
+            contract: dao.to_string(),
+            token_id: token_id.to_string(),
+            msg: Binary::default(),
+        })?,
+        funds: vec![],
+    };
+
+    let burn_msg = WasmMsg::Execute {
+        contract_addr: MEMBER_COLLECTION.load(store)?.to_string(),
+        msg: to_binary(&Cw721BaseExecuteMsg::Burn::<Empty, Empty> {
+            token_id: token_id.to_string(),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(vec![SubMsg::new(send_msg), SubMsg::new(burn_msg)])
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
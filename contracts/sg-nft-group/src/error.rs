@@ -38,4 +38,10 @@ pub enum ContractError {
 
     #[error("Reply error")]
     ReplyOnSuccess {},
+
+    #[error("No tokens given")]
+    NoTokens {},
+
+    #[error("The group is not administered by a DAO")]
+    NoDao {},
 }
@@ -290,7 +290,7 @@ mod tests {
     #[test]
     fn test_withdrawal() {
         let btc = coin(4, "BTC");
-        let mut app = mock_app(&[btc.clone()]);
+        let mut app = mock_app(std::slice::from_ref(&btc));
 
         let group_addr = setup(&mut app);
 
@@ -334,4 +334,36 @@ mod tests {
             assert_eq!(Uint128::from(response.weight.unwrap()), bal.amount);
         }
     }
+
+    #[test]
+    fn withdrawal_pays_every_member() {
+        let btc = coin(12, "BTC");
+        let mut app = mock_app(std::slice::from_ref(&btc));
+
+        let group_addr = setup(&mut app);
+        // more members than a page of `ListMembers`
+        let newcomers: Vec<_> = (0..8)
+            .map(|i| member(format!("newcomer{}", i), 1))
+            .collect();
+        mint_and_join_nft_group(&mut app, newcomers.clone());
+
+        app.send_tokens(Addr::unchecked(OWNER), group_addr.clone(), &[btc])
+            .unwrap();
+        app.execute_contract(
+            Addr::unchecked("anyone"),
+            group_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                denom: "BTC".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        for member in members().into_iter().chain(newcomers) {
+            let bal = app.wrap().query_balance(&member.addr, "BTC").unwrap();
+            assert_eq!(Uint128::from(member.weight), bal.amount);
+        }
+        let contract_bal = app.wrap().query_balance(&group_addr, "BTC").unwrap();
+        assert_eq!(contract_bal, coin(0, "BTC"));
+    }
 }
@@ -1,4 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw20::Denom;
 use cw721::Cw721ReceiveMsg;
 use sg_daos::ContractInstantiateMsg;
 
@@ -18,6 +19,15 @@ pub enum ExecuteMsg {
     Remove { token_id: String },
     /// Withdraw accidentally deposited tokens to NFT holders
     Withdraw { denom: String },
+    /// Distribute the attached funds to NFT holders
+    Distribute {},
+    /// Give up the NFTs to the DAO administering the group for a pro-rata share of
+    /// its treasury holdings of `denoms`.
+    /// Like `Remove`, the group must be approved to burn the membership NFTs.
+    Ragequit {
+        token_ids: Vec<String>,
+        denoms: Vec<Denom>,
+    },
 }
 
 #[cw_serde]
@@ -1,5 +1,8 @@
+pub mod swap;
+
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Binary, WasmMsg};
+use cosmwasm_std::{to_binary, Addr, Binary, CosmosMsg, StdResult, WasmMsg};
+use cw20::Denom;
 
 #[cw_serde]
 pub enum Admin {
@@ -29,3 +32,53 @@ impl ContractInstantiateMsg {
         }
     }
 }
+
+/// Sent by a group to its DAO when a member gives up `weight` in exchange for
+/// a pro-rata share of the treasury holdings of `denoms`
+#[cw_serde]
+pub struct RagequitMsg {
+    pub member: String,
+    pub weight: u64,
+    pub denoms: Vec<Denom>,
+}
+
+impl RagequitMsg {
+    pub fn into_cosmos_msg<T: Into<String>>(self, dao: T) -> StdResult<CosmosMsg> {
+        let msg = to_binary(&DaoExecuteMsg::Ragequit(self))?;
+        Ok(WasmMsg::Execute {
+            contract_addr: dao.into(),
+            msg,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+#[cw_serde]
+enum DaoExecuteMsg {
+    Ragequit(RagequitMsg),
+}
+
+/// Lists the sending DAO in a registry, or refreshes its entry after its metadata changed
+pub fn register_msg<T: Into<String>>(registry: T) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: registry.into(),
+        msg: to_binary(&RegistryExecuteMsg::Register {})?,
+        funds: vec![],
+    })
+}
+
+/// Removes the sending DAO from a registry
+pub fn deregister_msg<T: Into<String>>(registry: T) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: registry.into(),
+        msg: to_binary(&RegistryExecuteMsg::Deregister {})?,
+        funds: vec![],
+    })
+}
+
+#[cw_serde]
+enum RegistryExecuteMsg {
+    Register {},
+    Deregister {},
+}
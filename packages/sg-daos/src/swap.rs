@@ -0,0 +1,108 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    coins, to_binary, Addr, Binary, CosmosMsg, Decimal, StdError, StdResult, Uint128, WasmMsg,
+};
+
+#[cw_serde]
+pub enum AssetInfo {
+    Token { contract_addr: String },
+    NativeToken { denom: String },
+}
+
+#[cw_serde]
+pub struct Asset {
+    pub info: AssetInfo,
+    pub amount: Uint128,
+}
+
+/// Builds the messages swapping treasury funds on a DEX
+pub trait SwapAdapter {
+    /// Swap `offer` for the other asset of the pool, failing unless at least `min_receive`
+    /// is returned. Proceeds go to `to`, or the sender of the message if `None`.
+    fn swap_msg<T>(
+        &self,
+        offer: Asset,
+        min_receive: Uint128,
+        to: Option<String>,
+    ) -> StdResult<CosmosMsg<T>>;
+}
+
+/// Terraswap compatible pair messages, also spoken by Astroport and White Whale pairs
+#[cw_serde]
+pub enum PairExecuteMsg {
+    Swap {
+        offer_asset: Asset,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+/// Hook message of a cw20 `Send` to a pair
+#[cw_serde]
+pub enum PairCw20HookMsg {
+    Swap {
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+#[cw_serde]
+enum Cw20ExecuteMsg {
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+}
+
+/// A Terraswap compatible pair contract
+#[cw_serde]
+pub struct PairContract(pub Addr);
+
+impl SwapAdapter for PairContract {
+    fn swap_msg<T>(
+        &self,
+        offer: Asset,
+        min_receive: Uint128,
+        to: Option<String>,
+    ) -> StdResult<CosmosMsg<T>> {
+        if offer.amount.is_zero() || min_receive.is_zero() {
+            return Err(StdError::generic_err(
+                "offer and minimum receive amounts must be non-zero",
+            ));
+        }
+        // a price of exactly offer / min_receive with no spread allowed
+        // makes the pair reject any swap returning less than min_receive
+        let belief_price = Some(Decimal::from_ratio(offer.amount, min_receive));
+        let max_spread = Some(Decimal::zero());
+
+        let msg = match &offer.info {
+            AssetInfo::NativeToken { denom } => WasmMsg::Execute {
+                contract_addr: self.0.to_string(),
+                msg: to_binary(&PairExecuteMsg::Swap {
+                    offer_asset: offer.clone(),
+                    belief_price,
+                    max_spread,
+                    to,
+                })?,
+                funds: coins(offer.amount.u128(), denom),
+            },
+            AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+                contract_addr: contract_addr.clone(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: self.0.to_string(),
+                    amount: offer.amount,
+                    msg: to_binary(&PairCw20HookMsg::Swap {
+                        belief_price,
+                        max_spread,
+                        to,
+                    })?,
+                })?,
+                funds: vec![],
+            },
+        };
+        Ok(msg.into())
+    }
+}
@@ -15,3 +15,11 @@ starsd tx wasm store sg_gov.wasm --from $ADMIN \
 starsd tx wasm store sg_nft_group.wasm --from $ADMIN \
     --gas-prices 0.025ustars --gas-adjustment 1.7 \
     --gas auto -y -b block -o json | jq '.logs' | grep -A 1 code_id
+
+starsd tx wasm store sg_dao_factory.wasm --from $ADMIN \
+    --gas-prices 0.025ustars --gas-adjustment 1.7 \
+    --gas auto -y -b block -o json | jq '.logs' | grep -A 1 code_id
+
+starsd tx wasm store sg_dao_registry.wasm --from $ADMIN \
+    --gas-prices 0.025ustars --gas-adjustment 1.7 \
+    --gas auto -y -b block -o json | jq '.logs' | grep -A 1 code_id
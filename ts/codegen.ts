@@ -10,6 +10,14 @@ codegen({
       name: "SgNftGroup",
       dir: "../contracts/sg-nft-group/schema",
     },
+    {
+      name: "SgDaoFactory",
+      dir: "../contracts/sg-dao-factory/schema",
+    },
+    {
+      name: "SgDaoRegistry",
+      dir: "../contracts/sg-dao-registry/schema",
+    },
   ],
   outPath: "./src/",
 